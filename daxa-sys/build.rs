@@ -1,56 +1,138 @@
+#[cfg(feature = "bindgen")]
 extern crate bindgen;
 
-fn main() {
-    use std::env;
-    use std::path::PathBuf;
+use std::env;
+use std::path::{Path, PathBuf};
 
+fn main() {
     let static_crt = env::var("CARGO_ENCODED_RUSTFLAGS")
         .unwrap_or_default()
         .contains("target-feature=+crt-static");
-    let dst = cmake::Config::new("daxa")
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+
+    let mut config = cmake::Config::new("daxa");
+    config
         .build_target("daxa")
         .profile(get_profile())
         .configure_arg("--fresh")
-        .configure_arg("-DCMAKE_MODULE_PATH=~C:/Program Files/CMake/")
-        .configure_arg(
-            "-DCMAKE_TOOLCHAIN_FILE=C:/vcpkg/scripts/buildsystems/vcpkg.cmake",
-        )
         .configure_arg("-DBUILD_SHARED_LIBS=OFF")
-        .configure_arg("-DDAXA_USE_VCPKG=ON")
         .configure_arg(format!(
             "-DDAXA_USE_STATIC_CRT={}",
             if static_crt { 1 } else { 0 }
-        ))
-        .build();
-    let vulkan_path = env::var("VULKAN_SDK").unwrap();
+        ));
+
+    //daxa calls vulkan through volk's function pointers, filled in by daxa_sys_load_vulkan at runtime
+    if cfg!(feature = "dynamic-loader") {
+        let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+        config.define(
+            "CMAKE_PROJECT_INCLUDE",
+            Path::new(&manifest_dir).join("cmake/vulkan_loader.cmake"),
+        );
+    }
+
+    let mut include_paths = vec![PathBuf::from("daxa/include")];
+    let deps = if cfg!(feature = "vcpkg") {
+        Deps::vcpkg(&mut config)
+    } else {
+        Deps::system(&mut config)
+    };
+    let dst = config.build();
 
     println!(
         "cargo:rustc-link-search=native={}/build/{}",
         dst.display(),
         get_profile()
     );
-    println!(
-        "cargo:rustc-link-search=native={}/build/vcpkg_installed/{}/lib",
-        dst.display(),
-        get_vcpkg_os_dir()
-    );
-    println!("cargo:rustc-link-search=native={}/lib", vulkan_path);
+    println!("cargo:rustc-link-search=native={}/build", dst.display());
     println!("cargo:rustc-link-lib=static=daxa");
-    println!("cargo:rustc-link-lib=static=fmt");
-    println!("cargo:rustc-link-lib=static=vulkan-1");
+    if cfg!(feature = "dynamic-loader") {
+        println!("cargo:rustc-link-lib=static=daxa_sys_vulkan");
+    }
+
+    match deps {
+        Deps::Vcpkg { vulkan_sdk } => {
+            let vcpkg_installed = dst
+                .join("build/vcpkg_installed")
+                .join(get_vcpkg_triplet(&target_os, &target_arch));
+            println!(
+                "cargo:rustc-link-search=native={}",
+                vcpkg_installed.join("lib").display()
+            );
+            println!("cargo:rustc-link-lib=static=fmt");
+            if let Some(vulkan_sdk) = vulkan_sdk {
+                println!(
+                    "cargo:rustc-link-search=native={}",
+                    vulkan_sdk.join("lib").display()
+                );
+                include_paths.push(vulkan_sdk.join("include"));
+            }
+            include_paths.push(vcpkg_installed.join("include"));
+            link_vulkan(&target_os);
+        }
+        Deps::System { vulkan, fmt_found } => {
+            //pkg-config already printed the link lines for everything it found
+            if !fmt_found {
+                let fmt_build = dst.join("build/_deps/fmt-build");
+                println!("cargo:rustc-link-search=native={}", fmt_build.display());
+                println!(
+                    "cargo:rustc-link-search=native={}",
+                    fmt_build.join(get_profile()).display()
+                );
+                println!("cargo:rustc-link-lib=static=fmt");
+            }
+            match vulkan {
+                Vulkan::PkgConfig(library) => include_paths.extend(library.include_paths),
+                Vulkan::Sdk(vulkan_sdk) => {
+                    println!(
+                        "cargo:rustc-link-search=native={}",
+                        vulkan_sdk.join("lib").display()
+                    );
+                    include_paths.push(vulkan_sdk.join("include"));
+                    link_vulkan(&target_os);
+                }
+                Vulkan::SystemPaths => link_vulkan(&target_os),
+            }
+        }
+    }
+    link_cpp_runtime(&target_os);
+
     println!("cargo:rerun-if-changed=src/daxa.h");
     println!("cargo:rerun-if-changed=daxa");
+    println!("cargo:rerun-if-changed=cmake");
+    println!("cargo:rerun-if-env-changed=VULKAN_SDK");
+    println!("cargo:rerun-if-env-changed=VCPKG_ROOT");
 
-    let vcpkg_includes = format!(
-        "-I{}/build/vcpkg_installed/{}/include",
-        dst.display(),
-        get_vcpkg_os_dir()
+    //daxa.glsl, daxa.hlsl and daxa.inl, for shaders compiled at runtime
+    println!(
+        "cargo:rustc-env=DAXA_SYS_SHADER_INCLUDE_DIR={}",
+        Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("daxa/include")
+            .display()
     );
-    let bindings = bindgen::Builder::default()
-        .clang_arg("--target=x86_64-unknown-linux-gnu")
-        .clang_arg("--language=c")
-        .clang_arg("-Idaxa/include")
-        .clang_arg(vcpkg_includes)
+
+    emit_bindings(&include_paths);
+}
+
+//committed bindings live next to the sources, one file per target triple
+fn committed_bindings_path() -> PathBuf {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let target = env::var("TARGET").unwrap();
+    Path::new(&manifest_dir)
+        .join("src/bindings")
+        .join(format!("{}.rs", target))
+}
+
+#[cfg(feature = "bindgen")]
+fn emit_bindings(include_paths: &[PathBuf]) {
+    let target = env::var("TARGET").unwrap();
+    let mut builder = bindgen::Builder::default()
+        .clang_arg(format!("--target={}", target))
+        .clang_arg("--language=c");
+    for include_path in include_paths {
+        builder = builder.clang_arg(format!("-I{}", include_path.display()));
+    }
+    let bindings = builder
         .header("src/daxa.h")
         .parse_callbacks(Box::new(bindgen::CargoCallbacks))
         .generate()
@@ -59,16 +141,127 @@ fn main() {
     bindings
         .write_to_file(out_path.join("bindings.rs"))
         .expect("Couldn't write bindings!");
+
+    //read by the drift test in src/lib.rs
+    println!(
+        "cargo:rustc-env=DAXA_SYS_COMMITTED_BINDINGS={}",
+        committed_bindings_path().display()
+    );
+    if cfg!(feature = "regenerate-bindings") {
+        let committed = committed_bindings_path();
+        std::fs::create_dir_all(committed.parent().unwrap()).unwrap();
+        bindings
+            .write_to_file(&committed)
+            .expect("Couldn't write committed bindings!");
+    }
+}
+
+#[cfg(not(feature = "bindgen"))]
+fn emit_bindings(_include_paths: &[PathBuf]) {
+    let committed = committed_bindings_path();
+    println!("cargo:rerun-if-changed={}", committed.display());
+    if !committed.exists() {
+        panic!(
+            "no pre-generated bindings at {}, keep the default `bindgen` feature or generate them with \
+             `cargo regenerate-bindings --target {}`",
+            committed.display(),
+            env::var("TARGET").unwrap()
+        );
+    }
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    std::fs::copy(&committed, out_path.join("bindings.rs")).expect("Couldn't copy bindings!");
+}
+
+enum Vulkan {
+    PkgConfig(pkg_config::Library),
+    Sdk(PathBuf),
+    //headers and loader are expected in the default compiler and linker paths
+    SystemPaths,
+}
+
+enum Deps {
+    Vcpkg { vulkan_sdk: Option<PathBuf> },
+    System { vulkan: Vulkan, fmt_found: bool },
+}
+
+impl Deps {
+    fn vcpkg(config: &mut cmake::Config) -> Self {
+        let vcpkg_root = env::var("VCPKG_ROOT").unwrap_or_else(|_| String::from("C:/vcpkg"));
+        config
+            .configure_arg(format!(
+                "-DCMAKE_TOOLCHAIN_FILE={}/scripts/buildsystems/vcpkg.cmake",
+                vcpkg_root
+            ))
+            .configure_arg("-DDAXA_USE_VCPKG=ON");
+        Deps::Vcpkg {
+            vulkan_sdk: env::var_os("VULKAN_SDK").map(PathBuf::from),
+        }
+    }
+
+    fn system(config: &mut cmake::Config) -> Self {
+        config.configure_arg("-DDAXA_USE_VCPKG=OFF");
+
+        //an explicit sdk wins over whatever the distribution ships
+        let vulkan = if let Some(vulkan_sdk) = env::var_os("VULKAN_SDK") {
+            let vulkan_sdk = PathBuf::from(vulkan_sdk);
+            config.define("Vulkan_INCLUDE_DIR", vulkan_sdk.join("include"));
+            Vulkan::Sdk(vulkan_sdk)
+        } else if let Ok(library) = pkg_config::probe_library("vulkan") {
+            if let Some(include_path) = library.include_paths.first() {
+                config.define("Vulkan_INCLUDE_DIR", include_path);
+            }
+            Vulkan::PkgConfig(library)
+        } else {
+            Vulkan::SystemPaths
+        };
+
+        let fmt_found = pkg_config::probe_library("fmt").is_ok();
+        if !fmt_found {
+            let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+            config.define(
+                "CMAKE_PROJECT_daxa_INCLUDE",
+                Path::new(&manifest_dir).join("cmake/fetch_fmt.cmake"),
+            );
+        }
+
+        Deps::System { vulkan, fmt_found }
+    }
 }
 
-// TODO: Consideration must be made for other OSes and even cross-compile!
-#[cfg(target_os = "windows")]
-fn get_vcpkg_os_dir() -> &'static str {
-    "x64-windows"
+#[cfg(not(feature = "dynamic-loader"))]
+fn link_vulkan(target_os: &str) {
+    match target_os {
+        "windows" => println!("cargo:rustc-link-lib=static=vulkan-1"),
+        _ => println!("cargo:rustc-link-lib=dylib=vulkan"),
+    }
 }
-#[cfg(target_os = "linux")]
-fn get_vcpkg_os_dir() -> &'static str {
-    "x64-linux"
+
+//nothing to link, the loader is opened at runtime
+#[cfg(feature = "dynamic-loader")]
+fn link_vulkan(_target_os: &str) {}
+
+fn link_cpp_runtime(target_os: &str) {
+    match target_os {
+        "windows" => {}
+        "macos" | "ios" | "freebsd" => println!("cargo:rustc-link-lib=dylib=c++"),
+        _ => println!("cargo:rustc-link-lib=dylib=stdc++"),
+    }
+}
+
+// TODO: Consideration must be made for other architectures and even cross-compile!
+//vcpkg names architectures after msvc, so the rust names need translating
+fn get_vcpkg_triplet(target_os: &str, target_arch: &str) -> String {
+    let arch = match target_arch {
+        "x86_64" => "x64",
+        "aarch64" => "arm64",
+        other => other,
+    };
+    let os = match target_os {
+        "windows" => "windows",
+        "macos" => "osx",
+        _ => "linux",
+    };
+    format!("{}-{}", arch, os)
 }
 
 fn get_profile() -> &'static str {
@@ -0,0 +1,15 @@
+# Injected into daxa's project() by build.rs when no system fmt is found.
+# OVERRIDE_FIND_PACKAGE makes daxa's find_package(fmt) resolve to this checkout.
+include(FetchContent)
+
+set(FMT_INSTALL OFF CACHE BOOL "" FORCE)
+set(FMT_TEST OFF CACHE BOOL "" FORCE)
+set(CMAKE_POSITION_INDEPENDENT_CODE ON)
+
+FetchContent_Declare(
+    fmt
+    GIT_REPOSITORY https://github.com/fmtlib/fmt.git
+    GIT_TAG 10.1.1
+    OVERRIDE_FIND_PACKAGE
+)
+FetchContent_MakeAvailable(fmt)
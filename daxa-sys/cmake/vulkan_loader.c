@@ -0,0 +1,27 @@
+// Built into daxa_sys_vulkan by vulkan_loader.cmake, declared in src/lib.rs.
+#include <volk.h>
+
+static PFN_vkCreateInstance create_instance;
+
+// volk resolves instance functions per instance, so every instance daxa creates is handed to it.
+// The last instance wins, its pointers are the loader's trampolines and dispatch on any instance.
+static VkResult VKAPI_CALL create_instance_and_load(
+    VkInstanceCreateInfo const * info,
+    VkAllocationCallbacks const * allocator,
+    VkInstance * instance)
+{
+    VkResult result = create_instance(info, allocator, instance);
+    if (result == VK_SUCCESS)
+    {
+        volkLoadInstance(*instance);
+    }
+    return result;
+}
+
+// Called once, before the first daxa_create_instance.
+void daxa_sys_load_vulkan(PFN_vkGetInstanceProcAddr get_instance_proc_addr)
+{
+    volkInitializeCustom(get_instance_proc_addr);
+    create_instance = vkCreateInstance;
+    vkCreateInstance = create_instance_and_load;
+}
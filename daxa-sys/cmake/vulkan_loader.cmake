@@ -0,0 +1,42 @@
+# Injected into project() by build.rs with the dynamic-loader feature.
+# Compiles daxa with VK_NO_PROTOTYPES and volk.h force included, so every vk call goes through
+# volk's function pointers instead of the loader's exports. daxa_sys_load_vulkan fills them in.
+include_guard(GLOBAL)
+include(FetchContent)
+
+FetchContent_Declare(
+    volk
+    GIT_REPOSITORY https://github.com/zeux/volk.git
+    GIT_TAG 1.3.270
+)
+FetchContent_GetProperties(volk)
+if(NOT volk_POPULATED)
+    FetchContent_Populate(volk)
+endif()
+
+find_package(Vulkan REQUIRED)
+
+add_library(daxa_sys_vulkan STATIC
+    ${volk_SOURCE_DIR}/volk.c
+    ${CMAKE_CURRENT_LIST_DIR}/vulkan_loader.c
+)
+target_include_directories(daxa_sys_vulkan PUBLIC ${volk_SOURCE_DIR})
+target_link_libraries(daxa_sys_vulkan PUBLIC Vulkan::Headers)
+set_target_properties(daxa_sys_vulkan PROPERTIES POSITION_INDEPENDENT_CODE ON)
+
+# daxa's target only exists once its CMakeLists.txt is done
+function(daxa_sys_use_volk)
+    # vma takes the addresses of volk's pointers once they are loaded, instead of the missing prototypes
+    target_compile_definitions(daxa PRIVATE
+        VK_NO_PROTOTYPES
+        VMA_STATIC_VULKAN_FUNCTIONS=1
+        VMA_DYNAMIC_VULKAN_FUNCTIONS=0
+    )
+    if(MSVC)
+        target_compile_options(daxa PRIVATE /FIvolk.h)
+    else()
+        target_compile_options(daxa PRIVATE -include volk.h)
+    endif()
+    target_link_libraries(daxa PRIVATE daxa_sys_vulkan)
+endfunction()
+cmake_language(DEFER CALL daxa_sys_use_volk)
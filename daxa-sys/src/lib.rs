@@ -3,3 +3,34 @@
 #![allow(non_snake_case)]
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+//points into the daxa submodule this crate was built from
+pub const SHADER_INCLUDE_DIR: &str = env!("DAXA_SYS_SHADER_INCLUDE_DIR");
+
+//from cmake/vulkan_loader.c, daxa resolves every vulkan function through the given loader
+//call it once, before the first daxa_create_instance
+#[cfg(feature = "dynamic-loader")]
+extern "C" {
+    pub fn daxa_sys_load_vulkan(
+        get_instance_proc_addr: unsafe extern "system" fn(
+            VkInstance,
+            *const ::std::os::raw::c_char,
+        ) -> PFN_vkVoidFunction,
+    );
+}
+
+#[cfg(all(test, feature = "bindgen"))]
+mod tests {
+    #[test]
+    fn committed_bindings_match_header() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+        let committed_path = env!("DAXA_SYS_COMMITTED_BINDINGS");
+        let committed = std::fs::read_to_string(committed_path)
+            .unwrap_or_else(|_| panic!("missing committed bindings at {}", committed_path));
+        assert!(
+            generated == committed,
+            "{} is out of date with daxa/c/daxa.h, refresh it with `cargo regenerate-bindings`",
+            committed_path
+        );
+    }
+}
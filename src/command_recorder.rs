@@ -0,0 +1,1089 @@
+use bytemuck::Pod;
+use std::{any, mem, ptr};
+
+use crate::device::{Device, DeviceFlags};
+use crate::pipeline::{ComputePipeline, RasterPipeline};
+use crate::sync::Event;
+use crate::types::{self, assert_c_layout, *};
+
+#[derive(Clone, Debug, Default)]
+pub struct CommandRecorderInfo {
+    pub name: String,
+}
+
+impl AsCInfo for CommandRecorderInfo {
+    type C = daxa_sys::daxa_CommandRecorderInfo;
+
+    fn as_c_info(&self) -> std::result::Result<CInfo<Self::C>, InvalidName> {
+        CInfo::new(&self.name, |name| daxa_sys::daxa_CommandRecorderInfo {
+            name,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum PushConstantError {
+    //no pipeline was set on the recorder yet
+    NoPipelineBound {
+        push_constant: &'static str,
+    },
+    //the bound pipeline was created for a different push constant type
+    TypeMismatch {
+        push_constant: &'static str,
+        bound: &'static str,
+    },
+}
+
+impl std::fmt::Display for PushConstantError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for PushConstantError {}
+
+#[derive(Debug)]
+pub enum RenderPassError {
+    //more than MAX_COLOR_ATTACHMENTS, holds how many were given
+    TooManyColorAttachments(usize),
+    Daxa(DaxaError),
+}
+
+impl std::fmt::Display for RenderPassError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for RenderPassError {}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BufferCopyInfo {
+    pub src_buffer: BufferId,
+    pub src_offset: usize,
+    pub dst_buffer: BufferId,
+    pub dst_offset: usize,
+    pub size: usize,
+}
+
+impl BufferCopyInfo {
+    fn as_c(&self) -> daxa_sys::daxa_BufferCopyInfo {
+        daxa_sys::daxa_BufferCopyInfo {
+            src_buffer: self.src_buffer,
+            src_offset: self.src_offset as _,
+            dst_buffer: self.dst_buffer,
+            dst_offset: self.dst_offset as _,
+            size: self.size as _,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BufferImageCopyInfo {
+    pub buffer: BufferId,
+    pub buffer_offset: usize,
+    pub image: ImageId,
+    pub image_layout: ImageLayout,
+    pub image_slice: ImageArraySlice,
+    pub image_offset: Offset3D,
+    pub image_extent: Extent3D,
+}
+
+impl BufferImageCopyInfo {
+    fn as_c(&self) -> daxa_sys::daxa_BufferImageCopyInfo {
+        daxa_sys::daxa_BufferImageCopyInfo {
+            buffer: self.buffer,
+            buffer_offset: self.buffer_offset as _,
+            image: self.image,
+            image_layout: self.image_layout as _,
+            image_slice: unsafe { mem::transmute(self.image_slice) },
+            image_offset: self.image_offset.as_c(),
+            image_extent: self.image_extent.as_c(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ImageBufferCopyInfo {
+    pub image: ImageId,
+    pub image_layout: ImageLayout,
+    pub image_slice: ImageArraySlice,
+    pub image_offset: Offset3D,
+    pub image_extent: Extent3D,
+    pub buffer: BufferId,
+    pub buffer_offset: usize,
+}
+
+impl ImageBufferCopyInfo {
+    fn as_c(&self) -> daxa_sys::daxa_ImageBufferCopyInfo {
+        daxa_sys::daxa_ImageBufferCopyInfo {
+            image: self.image,
+            image_layout: self.image_layout as _,
+            image_slice: unsafe { mem::transmute(self.image_slice) },
+            image_offset: self.image_offset.as_c(),
+            image_extent: self.image_extent.as_c(),
+            buffer: self.buffer,
+            buffer_offset: self.buffer_offset as _,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ImageCopyInfo {
+    pub src_image: ImageId,
+    pub src_image_layout: ImageLayout,
+    pub src_slice: ImageArraySlice,
+    pub src_offset: Offset3D,
+    pub dst_image: ImageId,
+    pub dst_image_layout: ImageLayout,
+    pub dst_slice: ImageArraySlice,
+    pub dst_offset: Offset3D,
+    pub extent: Extent3D,
+}
+
+impl ImageCopyInfo {
+    fn as_c(&self) -> daxa_sys::daxa_ImageCopyInfo {
+        daxa_sys::daxa_ImageCopyInfo {
+            src_image: self.src_image,
+            src_image_layout: self.src_image_layout as _,
+            dst_image: self.dst_image,
+            dst_image_layout: self.dst_image_layout as _,
+            src_slice: unsafe { mem::transmute(self.src_slice) },
+            src_offset: self.src_offset.as_c(),
+            dst_slice: unsafe { mem::transmute(self.dst_slice) },
+            dst_offset: self.dst_offset.as_c(),
+            extent: self.extent.as_c(),
+        }
+    }
+}
+
+//offsets are the two opposite corners of the region, so blits can scale and flip
+#[derive(Clone, Copy, Debug)]
+pub struct ImageBlitInfo {
+    pub src_image: ImageId,
+    pub src_image_layout: ImageLayout,
+    pub src_slice: ImageArraySlice,
+    pub src_offsets: [Offset3D; 2],
+    pub dst_image: ImageId,
+    pub dst_image_layout: ImageLayout,
+    pub dst_slice: ImageArraySlice,
+    pub dst_offsets: [Offset3D; 2],
+    pub filter: Filter,
+}
+
+impl ImageBlitInfo {
+    fn as_c(&self) -> daxa_sys::daxa_ImageBlitInfo {
+        daxa_sys::daxa_ImageBlitInfo {
+            src_image: self.src_image,
+            src_image_layout: self.src_image_layout as _,
+            dst_image: self.dst_image,
+            dst_image_layout: self.dst_image_layout as _,
+            src_slice: unsafe { mem::transmute(self.src_slice) },
+            src_offsets: self.src_offsets.map(|offset| offset.as_c()),
+            dst_slice: unsafe { mem::transmute(self.dst_slice) },
+            dst_offsets: self.dst_offsets.map(|offset| offset.as_c()),
+            filter: self.filter.bits() as _,
+        }
+    }
+}
+
+//fills the range with a repeated 32 bit value, offset and size must be multiples of 4
+#[derive(Clone, Copy, Debug)]
+pub struct BufferClearInfo {
+    pub buffer: BufferId,
+    pub offset: usize,
+    pub size: usize,
+    pub clear_value: u32,
+}
+
+impl BufferClearInfo {
+    fn as_c(&self) -> daxa_sys::daxa_BufferClearInfo {
+        daxa_sys::daxa_BufferClearInfo {
+            buffer: self.buffer,
+            offset: self.offset as _,
+            size: self.size as _,
+            clear_value: self.clear_value,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ImageClearInfo {
+    pub image: ImageId,
+    pub image_layout: ImageLayout,
+    pub clear_value: ClearValue,
+    pub dst_slice: ImageMipArraySlice,
+}
+
+impl ImageClearInfo {
+    fn as_c(&self) -> daxa_sys::daxa_ImageClearInfo {
+        daxa_sys::daxa_ImageClearInfo {
+            image_layout: self.image_layout as _,
+            clear_value: self.clear_value.as_c(),
+            image: self.image,
+            dst_slice: unsafe { mem::transmute(self.dst_slice) },
+        }
+    }
+}
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttachmentLoadOp {
+    Load = daxa_sys::VkAttachmentLoadOp_VK_ATTACHMENT_LOAD_OP_LOAD,
+    Clear = daxa_sys::VkAttachmentLoadOp_VK_ATTACHMENT_LOAD_OP_CLEAR,
+    DontCare = daxa_sys::VkAttachmentLoadOp_VK_ATTACHMENT_LOAD_OP_DONT_CARE,
+}
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttachmentStoreOp {
+    Store = daxa_sys::VkAttachmentStoreOp_VK_ATTACHMENT_STORE_OP_STORE,
+    DontCare = daxa_sys::VkAttachmentStoreOp_VK_ATTACHMENT_STORE_OP_DONT_CARE,
+}
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndexType {
+    Uint16 = daxa_sys::VkIndexType_VK_INDEX_TYPE_UINT16,
+    Uint32 = daxa_sys::VkIndexType_VK_INDEX_TYPE_UINT32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RenderAttachmentInfo {
+    pub image_view: ImageViewId,
+    pub layout: ImageLayout,
+    pub load_op: AttachmentLoadOp,
+    pub store_op: AttachmentStoreOp,
+    pub clear_value: ClearValue,
+}
+
+impl RenderAttachmentInfo {
+    fn as_c(&self) -> CRenderAttachmentInfo {
+        CRenderAttachmentInfo {
+            image_view: self.image_view,
+            layout: self.layout,
+            load_op: self.load_op,
+            store_op: self.store_op,
+            clear_value: self.clear_value.as_c(),
+        }
+    }
+}
+
+pub const MAX_COLOR_ATTACHMENTS: usize = 8;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RenderPassBeginInfo<'a> {
+    pub color_attachments: &'a [RenderAttachmentInfo],
+    pub depth_attachment: std::option::Option<RenderAttachmentInfo>,
+    pub stencil_attachment: std::option::Option<RenderAttachmentInfo>,
+    pub render_area: Rect2D,
+}
+
+impl RenderPassBeginInfo<'_> {
+    fn as_c(&self) -> std::result::Result<CRenderPassBeginInfo, RenderPassError> {
+        if self.color_attachments.len() > MAX_COLOR_ATTACHMENTS {
+            return Err(RenderPassError::TooManyColorAttachments(
+                self.color_attachments.len(),
+            ));
+        }
+        //zero is load, store and undefined layout, so unused slots stay valid
+        let mut color_attachments: [CRenderAttachmentInfo; MAX_COLOR_ATTACHMENTS] =
+            unsafe { mem::zeroed() };
+        for (c_attachment, attachment) in color_attachments.iter_mut().zip(self.color_attachments) {
+            *c_attachment = attachment.as_c();
+        }
+        Ok(CRenderPassBeginInfo {
+            color_attachments,
+            color_attachment_count: self.color_attachments.len() as u8,
+            depth_attachment: self.depth_attachment.map(|a| a.as_c()).into(),
+            stencil_attachment: self.stencil_attachment.map(|a| a.as_c()).into(),
+            render_area: self.render_area,
+        })
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct CRenderAttachmentInfo {
+    image_view: ImageViewId,
+    layout: ImageLayout,
+    load_op: AttachmentLoadOp,
+    store_op: AttachmentStoreOp,
+    clear_value: daxa_sys::daxa_ClearValue,
+}
+
+#[repr(C)]
+struct CRenderPassBeginInfo {
+    color_attachments: [CRenderAttachmentInfo; MAX_COLOR_ATTACHMENTS],
+    color_attachment_count: u8,
+    depth_attachment: types::Option<CRenderAttachmentInfo>,
+    stencil_attachment: types::Option<CRenderAttachmentInfo>,
+    render_area: Rect2D,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SetIndexBufferInfo {
+    pub buffer: BufferId,
+    pub offset: usize,
+    pub index_type: IndexType,
+}
+
+impl SetIndexBufferInfo {
+    fn as_c(&self) -> daxa_sys::daxa_SetIndexBufferInfo {
+        daxa_sys::daxa_SetIndexBufferInfo {
+            buffer: self.buffer,
+            offset: self.offset as _,
+            index_type: self.index_type as _,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DrawInfo {
+    pub vertex_count: u32,
+    pub instance_count: u32,
+    pub first_vertex: u32,
+    pub first_instance: u32,
+}
+
+impl Default for DrawInfo {
+    fn default() -> Self {
+        Self {
+            vertex_count: 0,
+            instance_count: 1,
+            first_vertex: 0,
+            first_instance: 0,
+        }
+    }
+}
+
+impl DrawInfo {
+    fn as_c(&self) -> daxa_sys::daxa_DrawInfo {
+        daxa_sys::daxa_DrawInfo {
+            vertex_count: self.vertex_count,
+            instance_count: self.instance_count,
+            first_vertex: self.first_vertex,
+            first_instance: self.first_instance,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DrawIndexedInfo {
+    pub index_count: u32,
+    pub instance_count: u32,
+    pub first_index: u32,
+    pub vertex_offset: i32,
+    pub first_instance: u32,
+}
+
+impl Default for DrawIndexedInfo {
+    fn default() -> Self {
+        Self {
+            index_count: 0,
+            instance_count: 1,
+            first_index: 0,
+            vertex_offset: 0,
+            first_instance: 0,
+        }
+    }
+}
+
+impl DrawIndexedInfo {
+    fn as_c(&self) -> daxa_sys::daxa_DrawIndexedInfo {
+        daxa_sys::daxa_DrawIndexedInfo {
+            index_count: self.index_count,
+            instance_count: self.instance_count,
+            first_index: self.first_index,
+            vertex_offset: self.vertex_offset,
+            first_instance: self.first_instance,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DrawIndirectInfo {
+    pub draw_command_buffer: BufferId,
+    pub indirect_buffer_offset: usize,
+    pub draw_count: u32,
+    pub draw_command_stride: u32,
+    pub is_indexed: bool,
+}
+
+impl DrawIndirectInfo {
+    fn as_c(&self) -> daxa_sys::daxa_DrawIndirectInfo {
+        daxa_sys::daxa_DrawIndirectInfo {
+            draw_command_buffer: self.draw_command_buffer,
+            indirect_buffer_offset: self.indirect_buffer_offset as _,
+            draw_count: self.draw_count,
+            draw_command_stride: self.draw_command_stride,
+            is_indexed: self.is_indexed as _,
+        }
+    }
+}
+
+//the draw count is read from a buffer at execution time and clamped to max_draw_count
+#[derive(Clone, Copy, Debug)]
+pub struct DrawIndirectCountInfo {
+    pub draw_command_buffer: BufferId,
+    pub indirect_buffer_offset: usize,
+    pub draw_count_buffer: BufferId,
+    pub draw_count_buffer_offset: usize,
+    pub max_draw_count: u32,
+    pub draw_command_stride: u32,
+    pub is_indexed: bool,
+}
+
+impl DrawIndirectCountInfo {
+    fn as_c(&self) -> daxa_sys::daxa_DrawIndirectCountInfo {
+        daxa_sys::daxa_DrawIndirectCountInfo {
+            draw_command_buffer: self.draw_command_buffer,
+            indirect_buffer_offset: self.indirect_buffer_offset as _,
+            draw_count_buffer: self.draw_count_buffer,
+            draw_count_buffer_offset: self.draw_count_buffer_offset as _,
+            max_draw_count: self.max_draw_count,
+            draw_command_stride: self.draw_command_stride,
+            is_indexed: self.is_indexed as _,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DispatchInfo {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+impl Default for DispatchInfo {
+    fn default() -> Self {
+        Self { x: 1, y: 1, z: 1 }
+    }
+}
+
+impl DispatchInfo {
+    fn as_c(&self) -> daxa_sys::daxa_DispatchInfo {
+        daxa_sys::daxa_DispatchInfo {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DispatchIndirectInfo {
+    pub indirect_buffer: BufferId,
+    pub offset: usize,
+}
+
+impl DispatchIndirectInfo {
+    fn as_c(&self) -> daxa_sys::daxa_DispatchIndirectInfo {
+        daxa_sys::daxa_DispatchIndirectInfo {
+            indirect_buffer: self.indirect_buffer,
+            offset: self.offset as _,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MemoryBarrierInfo {
+    pub src_access: Access,
+    pub dst_access: Access,
+}
+
+//transitions the slice from src_layout to dst_layout, Undefined as src_layout discards the contents
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ImageMemoryBarrierInfo {
+    pub src_access: Access,
+    pub dst_access: Access,
+    pub src_layout: ImageLayout,
+    pub dst_layout: ImageLayout,
+    pub image_slice: ImageMipArraySlice,
+    pub image: ImageId,
+}
+
+//the first half of a split barrier, the matching wait_events call completes it
+#[derive(Clone, Copy)]
+pub struct EventSignalInfo<'a> {
+    pub memory_barriers: &'a [MemoryBarrierInfo],
+    pub image_barriers: &'a [ImageMemoryBarrierInfo],
+    pub event: &'a Event,
+}
+
+pub type EventWaitInfo<'a> = EventSignalInfo<'a>;
+
+impl EventSignalInfo<'_> {
+    fn as_c(&self) -> CEventInfo {
+        CEventInfo {
+            memory_barriers: self.memory_barriers.as_ptr(),
+            memory_barrier_count: self.memory_barriers.len() as u64,
+            image_barriers: self.image_barriers.as_ptr(),
+            image_barrier_count: self.image_barriers.len() as u64,
+            event: self.event.handle(),
+        }
+    }
+}
+
+#[repr(C)]
+struct CEventInfo {
+    memory_barriers: *const MemoryBarrierInfo,
+    memory_barrier_count: u64,
+    image_barriers: *const ImageMemoryBarrierInfo,
+    image_barrier_count: u64,
+    event: *const daxa_sys::daxa_Event,
+}
+
+#[repr(C)]
+struct CResetEventInfo {
+    event: *const daxa_sys::daxa_Event,
+    stages: PipelineStageFlags,
+}
+
+//the aspects a barrier over the whole image covers, like daxa infers them for its own barriers
+fn aspect_mask(format: daxa_sys::VkFormat) -> daxa_sys::VkImageAspectFlags {
+    let depth = daxa_sys::VkImageAspectFlagBits_VK_IMAGE_ASPECT_DEPTH_BIT;
+    let stencil = daxa_sys::VkImageAspectFlagBits_VK_IMAGE_ASPECT_STENCIL_BIT;
+    match format {
+        daxa_sys::VkFormat_VK_FORMAT_D16_UNORM
+        | daxa_sys::VkFormat_VK_FORMAT_X8_D24_UNORM_PACK32
+        | daxa_sys::VkFormat_VK_FORMAT_D32_SFLOAT => depth,
+        daxa_sys::VkFormat_VK_FORMAT_S8_UINT => stencil,
+        daxa_sys::VkFormat_VK_FORMAT_D16_UNORM_S8_UINT
+        | daxa_sys::VkFormat_VK_FORMAT_D24_UNORM_S8_UINT
+        | daxa_sys::VkFormat_VK_FORMAT_D32_SFLOAT_S8_UINT => depth | stencil,
+        _ => daxa_sys::VkImageAspectFlagBits_VK_IMAGE_ASPECT_COLOR_BIT,
+    }
+}
+
+//remembers the layout each image was last transitioned to in this recorder, debug builds only
+#[derive(Default)]
+struct LayoutTracker {
+    #[cfg(debug_assertions)]
+    layouts: std::collections::HashMap<u64, ImageLayout>,
+}
+
+#[cfg(debug_assertions)]
+impl LayoutTracker {
+    fn key(image: ImageId) -> u64 {
+        unsafe { mem::transmute(image) }
+    }
+
+    fn transition(&mut self, image: ImageId, layout: ImageLayout) {
+        self.layouts.insert(Self::key(image), layout);
+    }
+
+    fn check(&self, image: ImageId, layout: ImageLayout, operation: &str, recorder: &str) {
+        match self.layouts.get(&Self::key(image)) {
+            Some(&last) if last != layout => log::warn!(
+                "{} in command recorder \"{}\" uses image {:?} as {:?}, but it was last transitioned to {:?}",
+                operation, recorder, image, layout, last
+            ),
+            _ => {}
+        }
+    }
+
+    //attachments stay in their render pass layout, so later uses are checked against it
+    fn attachments(&mut self, device: &Device, info: &RenderPassBeginInfo, recorder: &str) {
+        let attachments = info
+            .color_attachments
+            .iter()
+            .chain(&info.depth_attachment)
+            .chain(&info.stencil_attachment);
+        for attachment in attachments {
+            let Some(image) = device.image_view_image(attachment.image_view) else {
+                continue;
+            };
+            self.check(image, attachment.layout, "begin_renderpass", recorder);
+            self.transition(image, attachment.layout);
+        }
+    }
+}
+
+#[cfg(not(debug_assertions))]
+impl LayoutTracker {
+    fn transition(&mut self, _: ImageId, _: ImageLayout) {}
+
+    fn check(&self, _: ImageId, _: ImageLayout, _: &str, _: &str) {}
+
+    fn attachments(&mut self, _: &Device, _: &RenderPassBeginInfo, _: &str) {}
+}
+
+//records commands for a single queue submission, complete turns them into something submittable
+pub struct CommandRecorder {
+    handle: daxa_sys::daxa_CommandRecorder,
+    info: CommandRecorderInfo,
+    device: Device,
+    //push constant type of the last bound pipeline
+    bound_push_constant: std::option::Option<(any::TypeId, &'static str)>,
+    layouts: LayoutTracker,
+}
+
+impl CommandRecorder {
+    pub(crate) fn from_raw(
+        device: Device,
+        handle: daxa_sys::daxa_CommandRecorder,
+        info: CommandRecorderInfo,
+    ) -> Self {
+        Self {
+            handle,
+            info,
+            device,
+            bound_push_constant: None,
+            layouts: LayoutTracker::default(),
+        }
+    }
+
+    fn bind_push_constant<P: Pod>(&mut self) {
+        self.bound_push_constant = Some((any::TypeId::of::<P>(), any::type_name::<P>()));
+    }
+
+    //refuses instead of letting a mismatched layout silently corrupt the shader's view of the data
+    fn record_push_constant<P: Pod>(
+        &mut self,
+        value: &P,
+    ) -> std::result::Result<(), PushConstantError> {
+        let push_constant = any::type_name::<P>();
+        match self.bound_push_constant {
+            Some((type_id, _)) if type_id == any::TypeId::of::<P>() => {}
+            Some((_, bound)) => {
+                return Err(PushConstantError::TypeMismatch {
+                    push_constant,
+                    bound,
+                })
+            }
+            None => return Err(PushConstantError::NoPipelineBound { push_constant }),
+        }
+        let bytes = bytemuck::bytes_of(value);
+        //vulkan has no empty push constant ranges, pipelines without push constants have nothing to push
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        unsafe {
+            daxa_sys::daxa_cmd_push_constant(
+                self.handle,
+                bytes.as_ptr().cast(),
+                bytes.len() as u32,
+                0,
+            )
+        }
+        Ok(())
+    }
+
+    pub fn info(&self) -> &CommandRecorderInfo {
+        &self.info
+    }
+
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    pub fn copy_buffer_to_buffer(
+        &mut self,
+        info: &BufferCopyInfo,
+    ) -> std::result::Result<(), DaxaError> {
+        let c_info = info.as_c();
+        let c_result = unsafe { daxa_sys::daxa_cmd_copy_buffer_to_buffer(self.handle, &c_info) };
+        DaxaError::check(c_result, "copy_buffer_to_buffer", &self.info.name)
+    }
+
+    pub fn copy_buffer_to_image(
+        &mut self,
+        info: &BufferImageCopyInfo,
+    ) -> std::result::Result<(), DaxaError> {
+        self.check_layout(info.image, info.image_layout, "copy_buffer_to_image");
+        let c_info = info.as_c();
+        let c_result = unsafe { daxa_sys::daxa_cmd_copy_buffer_to_image(self.handle, &c_info) };
+        DaxaError::check(c_result, "copy_buffer_to_image", &self.info.name)
+    }
+
+    pub fn copy_image_to_buffer(
+        &mut self,
+        info: &ImageBufferCopyInfo,
+    ) -> std::result::Result<(), DaxaError> {
+        self.check_layout(info.image, info.image_layout, "copy_image_to_buffer");
+        let c_info = info.as_c();
+        let c_result = unsafe { daxa_sys::daxa_cmd_copy_image_to_buffer(self.handle, &c_info) };
+        DaxaError::check(c_result, "copy_image_to_buffer", &self.info.name)
+    }
+
+    pub fn copy_image_to_image(
+        &mut self,
+        info: &ImageCopyInfo,
+    ) -> std::result::Result<(), DaxaError> {
+        self.check_layout(info.src_image, info.src_image_layout, "copy_image_to_image");
+        self.check_layout(info.dst_image, info.dst_image_layout, "copy_image_to_image");
+        let c_info = info.as_c();
+        let c_result = unsafe { daxa_sys::daxa_cmd_copy_image_to_image(self.handle, &c_info) };
+        DaxaError::check(c_result, "copy_image_to_image", &self.info.name)
+    }
+
+    pub fn blit_image_to_image(
+        &mut self,
+        info: &ImageBlitInfo,
+    ) -> std::result::Result<(), DaxaError> {
+        self.check_layout(info.src_image, info.src_image_layout, "blit_image_to_image");
+        self.check_layout(info.dst_image, info.dst_image_layout, "blit_image_to_image");
+        let c_info = info.as_c();
+        let c_result = unsafe { daxa_sys::daxa_cmd_blit_image_to_image(self.handle, &c_info) };
+        DaxaError::check(c_result, "blit_image_to_image", &self.info.name)
+    }
+
+    pub fn clear_buffer(&mut self, info: &BufferClearInfo) -> std::result::Result<(), DaxaError> {
+        let c_info = info.as_c();
+        let c_result = unsafe { daxa_sys::daxa_cmd_clear_buffer(self.handle, &c_info) };
+        DaxaError::check(c_result, "clear_buffer", &self.info.name)
+    }
+
+    pub fn clear_image(&mut self, info: &ImageClearInfo) -> std::result::Result<(), DaxaError> {
+        self.check_layout(info.image, info.image_layout, "clear_image");
+        let c_info = info.as_c();
+        let c_result = unsafe { daxa_sys::daxa_cmd_clear_image(self.handle, &c_info) };
+        DaxaError::check(c_result, "clear_image", &self.info.name)
+    }
+
+    fn check_layout(&self, image: ImageId, layout: ImageLayout, operation: &str) {
+        self.layouts
+            .check(image, layout, operation, &self.info.name);
+    }
+
+    fn check_transitions(&self, image_barriers: &[ImageMemoryBarrierInfo], operation: &str) {
+        for barrier in image_barriers {
+            if barrier.src_layout != ImageLayout::Undefined {
+                self.check_layout(barrier.image, barrier.src_layout, operation);
+            }
+        }
+    }
+
+    //nothing is recorded unless every image is alive and every event belongs to this device
+    fn check_barrier_inputs<'a>(
+        &self,
+        image_barriers: impl IntoIterator<Item = &'a ImageMemoryBarrierInfo>,
+        events: impl IntoIterator<Item = &'a Event>,
+        operation: &'static str,
+    ) -> std::result::Result<(), DaxaError> {
+        let mut image_barriers = image_barriers.into_iter();
+        if image_barriers.any(|barrier| !self.device.is_image_valid(barrier.image)) {
+            return Err(DaxaError::new(
+                Result::InvalidImageId as i32,
+                operation,
+                &self.info.name,
+            ));
+        }
+        let mut events = events.into_iter();
+        if events.any(|event| event.device().vk_device() != self.device.vk_device()) {
+            return Err(DaxaError::new(
+                Result::ErrorValidationFailedExt as i32,
+                operation,
+                &self.info.name,
+            ));
+        }
+        Ok(())
+    }
+
+    //recorded as a single vkCmdPipelineBarrier2, daxa itself only takes one barrier per call
+    pub fn pipeline_barriers(
+        &mut self,
+        memory_barriers: &[MemoryBarrierInfo],
+        image_barriers: &[ImageMemoryBarrierInfo],
+    ) -> std::result::Result<(), DaxaError> {
+        self.check_transitions(image_barriers, "pipeline_barriers");
+        if memory_barriers.is_empty() && image_barriers.is_empty() {
+            return Ok(());
+        }
+        let Some(cmd_pipeline_barrier2) = self.device.vk_cmd_pipeline_barrier2() else {
+            return Err(DaxaError::new(
+                daxa_sys::daxa_Result_DAXA_RESULT_ERROR_EXTENSION_NOT_PRESENT,
+                "pipeline_barriers",
+                &self.info.name,
+            ));
+        };
+
+        let vk_memory_barriers = memory_barriers
+            .iter()
+            .map(|barrier| daxa_sys::VkMemoryBarrier2 {
+                sType: daxa_sys::VkStructureType_VK_STRUCTURE_TYPE_MEMORY_BARRIER_2,
+                pNext: ptr::null(),
+                srcStageMask: barrier.src_access.stages.bits(),
+                srcAccessMask: barrier.src_access.access_type.bits(),
+                dstStageMask: barrier.dst_access.stages.bits(),
+                dstAccessMask: barrier.dst_access.access_type.bits(),
+            })
+            .collect::<Vec<_>>();
+        let vk_image_barriers = image_barriers
+            .iter()
+            .map(|barrier| {
+                let (image, format) = self.device.vk_image(barrier.image).ok_or_else(|| {
+                    DaxaError::new(
+                        Result::InvalidImageId as i32,
+                        "pipeline_barriers",
+                        &self.info.name,
+                    )
+                })?;
+                Ok(daxa_sys::VkImageMemoryBarrier2 {
+                    sType: daxa_sys::VkStructureType_VK_STRUCTURE_TYPE_IMAGE_MEMORY_BARRIER_2,
+                    pNext: ptr::null(),
+                    srcStageMask: barrier.src_access.stages.bits(),
+                    srcAccessMask: barrier.src_access.access_type.bits(),
+                    dstStageMask: barrier.dst_access.stages.bits(),
+                    dstAccessMask: barrier.dst_access.access_type.bits(),
+                    //daxa's layouts carry vulkan's values
+                    oldLayout: barrier.src_layout as _,
+                    newLayout: barrier.dst_layout as _,
+                    //VK_QUEUE_FAMILY_IGNORED, no ownership transfer
+                    srcQueueFamilyIndex: u32::MAX,
+                    dstQueueFamilyIndex: u32::MAX,
+                    image,
+                    subresourceRange: daxa_sys::VkImageSubresourceRange {
+                        aspectMask: aspect_mask(format),
+                        baseMipLevel: barrier.image_slice.base_mip_level,
+                        levelCount: barrier.image_slice.level_count,
+                        baseArrayLayer: barrier.image_slice.base_array_layer,
+                        layerCount: barrier.image_slice.layer_count,
+                    },
+                })
+            })
+            .collect::<std::result::Result<Vec<_>, DaxaError>>()?;
+        let dependency_info = daxa_sys::VkDependencyInfo {
+            sType: daxa_sys::VkStructureType_VK_STRUCTURE_TYPE_DEPENDENCY_INFO,
+            pNext: ptr::null(),
+            dependencyFlags: 0,
+            memoryBarrierCount: vk_memory_barriers.len() as u32,
+            pMemoryBarriers: vk_memory_barriers.as_ptr(),
+            bufferMemoryBarrierCount: 0,
+            pBufferMemoryBarriers: ptr::null(),
+            imageMemoryBarrierCount: vk_image_barriers.len() as u32,
+            pImageMemoryBarriers: vk_image_barriers.as_ptr(),
+        };
+        unsafe {
+            cmd_pipeline_barrier2(
+                daxa_sys::daxa_cmd_get_vk_command_buffer(self.handle),
+                &dependency_info,
+            )
+        }
+        for barrier in image_barriers {
+            self.layouts.transition(barrier.image, barrier.dst_layout);
+        }
+        Ok(())
+    }
+
+    pub fn signal_event(&mut self, info: &EventSignalInfo) -> std::result::Result<(), DaxaError> {
+        self.check_barrier_inputs(info.image_barriers, [info.event], "signal_event")?;
+        self.check_transitions(info.image_barriers, "signal_event");
+        let c_info = info.as_c();
+        unsafe {
+            daxa_sys::daxa_cmd_signal_event(self.handle, (&c_info as *const CEventInfo).cast())
+        }
+        Ok(())
+    }
+
+    //image layouts of a split barrier only change once the wait has been recorded
+    pub fn wait_events(&mut self, infos: &[EventWaitInfo]) -> std::result::Result<(), DaxaError> {
+        self.check_barrier_inputs(
+            infos.iter().flat_map(|info| info.image_barriers),
+            infos.iter().map(|info| info.event),
+            "wait_events",
+        )?;
+        let c_infos = infos.iter().map(|info| info.as_c()).collect::<Vec<_>>();
+        unsafe {
+            daxa_sys::daxa_cmd_wait_events(self.handle, c_infos.as_ptr().cast(), c_infos.len() as _)
+        }
+        for barrier in infos.iter().flat_map(|info| info.image_barriers) {
+            self.layouts.transition(barrier.image, barrier.dst_layout);
+        }
+        Ok(())
+    }
+
+    pub fn reset_event(
+        &mut self,
+        event: &Event,
+        stages: PipelineStageFlags,
+    ) -> std::result::Result<(), DaxaError> {
+        self.check_barrier_inputs([], [event], "reset_event")?;
+        let c_info = CResetEventInfo {
+            event: event.handle(),
+            stages,
+        };
+        unsafe {
+            daxa_sys::daxa_cmd_reset_event(self.handle, (&c_info as *const CResetEventInfo).cast())
+        }
+        Ok(())
+    }
+
+    //the returned recorder ends the render pass when dropped, until then only draws can be recorded
+    pub fn begin_renderpass(
+        &mut self,
+        info: &RenderPassBeginInfo,
+    ) -> std::result::Result<RenderCommandRecorder<'_>, RenderPassError> {
+        let c_info = info.as_c()?;
+        self.layouts
+            .attachments(&self.device, info, &self.info.name);
+        let c_result = unsafe {
+            daxa_sys::daxa_cmd_begin_renderpass(
+                self.handle,
+                (&c_info as *const CRenderPassBeginInfo).cast(),
+            )
+        };
+        DaxaError::check(c_result, "begin_renderpass", &self.info.name)
+            .map_err(RenderPassError::Daxa)?;
+        Ok(RenderCommandRecorder { recorder: self })
+    }
+
+    pub fn set_pipeline<P: Pod>(&mut self, pipeline: &ComputePipeline<P>) {
+        unsafe { daxa_sys::daxa_cmd_set_compute_pipeline(self.handle, pipeline.handle()) }
+        self.bind_push_constant::<P>();
+    }
+
+    //P must be the push constant type of the currently bound pipeline
+    pub fn push_constant<P: Pod>(
+        &mut self,
+        value: &P,
+    ) -> std::result::Result<(), PushConstantError> {
+        self.record_push_constant(value)
+    }
+
+    pub fn dispatch(&mut self, info: &DispatchInfo) {
+        let c_info = info.as_c();
+        unsafe { daxa_sys::daxa_cmd_dispatch(self.handle, &c_info) }
+    }
+
+    pub fn dispatch_indirect(
+        &mut self,
+        info: &DispatchIndirectInfo,
+    ) -> std::result::Result<(), DaxaError> {
+        let c_info = info.as_c();
+        let c_result = unsafe { daxa_sys::daxa_cmd_dispatch_indirect(self.handle, &c_info) };
+        DaxaError::check(c_result, "dispatch_indirect", &self.info.name)
+    }
+
+    pub fn complete(self) -> std::result::Result<ExecutableCommandList, DaxaError> {
+        unsafe {
+            let mut handle = mem::zeroed();
+
+            let c_result = daxa_sys::daxa_cmd_complete_current_commands(self.handle, &mut handle);
+
+            DaxaError::check(c_result, "complete", &self.info.name)?;
+            Ok(ExecutableCommandList {
+                handle,
+                _device: self.device.clone(),
+            })
+        }
+    }
+}
+
+impl Drop for CommandRecorder {
+    fn drop(&mut self) {
+        unsafe { daxa_sys::daxa_destroy_command_recorder(self.handle) }
+    }
+}
+
+//borrows the recorder for the duration of a render pass, so dispatches and transfers cannot be recorded inside it
+pub struct RenderCommandRecorder<'a> {
+    recorder: &'a mut CommandRecorder,
+}
+
+impl RenderCommandRecorder<'_> {
+    pub fn set_pipeline<P: Pod>(&mut self, pipeline: &RasterPipeline<P>) {
+        unsafe { daxa_sys::daxa_cmd_set_raster_pipeline(self.recorder.handle, pipeline.handle()) }
+        self.recorder.bind_push_constant::<P>();
+    }
+
+    //P must be the push constant type of the currently bound pipeline
+    pub fn push_constant<P: Pod>(
+        &mut self,
+        value: &P,
+    ) -> std::result::Result<(), PushConstantError> {
+        self.recorder.record_push_constant(value)
+    }
+
+    pub fn set_index_buffer(&mut self, info: &SetIndexBufferInfo) {
+        let c_info = info.as_c();
+        unsafe { daxa_sys::daxa_cmd_set_index_buffer(self.recorder.handle, &c_info) }
+    }
+
+    pub fn draw(&mut self, info: &DrawInfo) {
+        let c_info = info.as_c();
+        unsafe { daxa_sys::daxa_cmd_draw(self.recorder.handle, &c_info) }
+    }
+
+    pub fn draw_indexed(&mut self, info: &DrawIndexedInfo) {
+        let c_info = info.as_c();
+        unsafe { daxa_sys::daxa_cmd_draw_indexed(self.recorder.handle, &c_info) }
+    }
+
+    pub fn draw_indirect(&mut self, info: &DrawIndirectInfo) -> std::result::Result<(), DaxaError> {
+        let c_info = info.as_c();
+        let c_result = unsafe { daxa_sys::daxa_cmd_draw_indirect(self.recorder.handle, &c_info) };
+        DaxaError::check(c_result, "draw_indirect", &self.recorder.info.name)
+    }
+
+    pub fn draw_indirect_count(
+        &mut self,
+        info: &DrawIndirectCountInfo,
+    ) -> std::result::Result<(), DaxaError> {
+        let c_info = info.as_c();
+        let c_result =
+            unsafe { daxa_sys::daxa_cmd_draw_indirect_count(self.recorder.handle, &c_info) };
+        DaxaError::check(c_result, "draw_indirect_count", &self.recorder.info.name)
+    }
+
+    //needs a device created with DeviceFlags::MESH_SHADER_BIT
+    pub fn draw_mesh_tasks(
+        &mut self,
+        x: u32,
+        y: u32,
+        z: u32,
+    ) -> std::result::Result<(), DaxaError> {
+        if !self
+            .recorder
+            .device
+            .info()
+            .flags()
+            .contains(DeviceFlags::MESH_SHADER_BIT)
+        {
+            return Err(DaxaError::new(
+                Result::ErrorFeatureNotPresent as i32,
+                "draw_mesh_tasks",
+                self.recorder.info.name.as_str(),
+            ));
+        }
+        unsafe { daxa_sys::daxa_cmd_draw_mesh_tasks(self.recorder.handle, x, y, z) }
+        Ok(())
+    }
+
+    pub fn end(self) {}
+}
+
+impl Drop for RenderCommandRecorder<'_> {
+    fn drop(&mut self) {
+        unsafe { daxa_sys::daxa_cmd_end_renderpass(self.recorder.handle) }
+    }
+}
+
+//finished commands, ready to be handed to a submit
+pub struct ExecutableCommandList {
+    handle: daxa_sys::daxa_ExecutableCommandList,
+    _device: Device,
+}
+
+impl ExecutableCommandList {
+    pub(crate) fn handle(&self) -> daxa_sys::daxa_ExecutableCommandList {
+        self.handle
+    }
+}
+
+impl Drop for ExecutableCommandList {
+    fn drop(&mut self) {
+        unsafe { daxa_sys::daxa_destroy_executable_command_list(self.handle) }
+    }
+}
+
+assert_c_layout!(CRenderAttachmentInfo, daxa_sys::daxa_RenderAttachmentInfo);
+assert_c_layout!(CRenderPassBeginInfo, daxa_sys::daxa_RenderPassBeginInfo);
+assert_c_layout!(MemoryBarrierInfo, daxa_sys::daxa_MemoryBarrierInfo);
+assert_c_layout!(
+    ImageMemoryBarrierInfo,
+    daxa_sys::daxa_ImageMemoryBarrierInfo
+);
+assert_c_layout!(CEventInfo, daxa_sys::daxa_EventSignalInfo);
+assert_c_layout!(CResetEventInfo, daxa_sys::daxa_ResetEventInfo);
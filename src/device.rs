@@ -1,16 +1,56 @@
 use bitflags::bitflags;
+use bytemuck::Pod;
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use std::cell;
+use std::ffi;
 use std::mem;
+use std::panic;
 use std::sync;
 
+use crate::command_recorder::{CommandRecorder, CommandRecorderInfo};
+use crate::future::{GpuFuture, GpuWaiter};
+use crate::instance::Instance;
+use crate::loader;
+use crate::pipeline::{
+    self, ComputePipeline, ComputePipelineInfo, PipelineCreateError, RasterPipeline,
+    RasterPipelineInfo,
+};
+use crate::swapchain::{
+    surface_formats, HeadlessPresentCallback, HeadlessSwapchain, NativeWindow, Swapchain,
+    SwapchainCreateError, SwapchainInfo,
+};
+use crate::sync::{
+    BinarySemaphore, BinarySemaphoreInfo, Event, EventInfo, TimelineSemaphore,
+    TimelineSemaphoreInfo,
+};
 use crate::types::*;
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DeviceType {
-    Other = daxa_sys::daxa_DeviceType_DAXA_DEVICE_TYPE_OTHER,
-    IntegratedGpu = daxa_sys::daxa_DeviceType_DAXA_DEVICE_TYPE_INTEGRATED_GPU,
-    DiscreteGpu = daxa_sys::daxa_DeviceType_DAXA_DEVICE_TYPE_DISCRETE_GPU,
-    VirtualGpu = daxa_sys::daxa_DeviceType_DAXA_DEVICE_TYPE_VIRTUAL_GPU,
-    Cpu = daxa_sys::daxa_DeviceType_DAXA_DEVICE_TYPE_CPU,
+    Other = daxa_sys::VkPhysicalDeviceType_VK_PHYSICAL_DEVICE_TYPE_OTHER,
+    IntegratedGpu = daxa_sys::VkPhysicalDeviceType_VK_PHYSICAL_DEVICE_TYPE_INTEGRATED_GPU,
+    DiscreteGpu = daxa_sys::VkPhysicalDeviceType_VK_PHYSICAL_DEVICE_TYPE_DISCRETE_GPU,
+    VirtualGpu = daxa_sys::VkPhysicalDeviceType_VK_PHYSICAL_DEVICE_TYPE_VIRTUAL_GPU,
+    Cpu = daxa_sys::VkPhysicalDeviceType_VK_PHYSICAL_DEVICE_TYPE_CPU,
+}
+
+impl DeviceType {
+    pub(crate) fn from_c(ty: daxa_sys::VkPhysicalDeviceType) -> Self {
+        match ty {
+            daxa_sys::VkPhysicalDeviceType_VK_PHYSICAL_DEVICE_TYPE_INTEGRATED_GPU => {
+                DeviceType::IntegratedGpu
+            }
+            daxa_sys::VkPhysicalDeviceType_VK_PHYSICAL_DEVICE_TYPE_DISCRETE_GPU => {
+                DeviceType::DiscreteGpu
+            }
+            daxa_sys::VkPhysicalDeviceType_VK_PHYSICAL_DEVICE_TYPE_VIRTUAL_GPU => {
+                DeviceType::VirtualGpu
+            }
+            daxa_sys::VkPhysicalDeviceType_VK_PHYSICAL_DEVICE_TYPE_CPU => DeviceType::Cpu,
+            _ => DeviceType::Other,
+        }
+    }
 }
 
 bitflags! {
@@ -25,14 +65,150 @@ bitflags! {
     }
 }
 
-pub type DeviceSelector = extern "C" fn(*const VkPhysicalDeviceProperties) -> i32;
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryHeap {
+    pub size: DeviceSize,
+    pub device_local: bool,
+}
+
+//everything daxa cares about when picking an adapter, as reported by Instance::enumerate_physical_devices
+#[derive(Clone)]
+pub struct PhysicalDevice {
+    pub(crate) properties: PhysicalDeviceProperties,
+    pub(crate) features: DeviceFlags,
+    pub(crate) memory_heaps: Vec<MemoryHeap>,
+    pub(crate) unsupported_reasons: Vec<&'static str>,
+}
+
+impl PhysicalDevice {
+    pub fn properties(&self) -> &PhysicalDeviceProperties {
+        &self.properties
+    }
+
+    pub fn limits(&self) -> &VkPhysicalDeviceLimits {
+        self.properties.limits()
+    }
+
+    //the optional device flags this adapter can be created with
+    pub fn features(&self) -> DeviceFlags {
+        self.features
+    }
+
+    pub fn memory_heaps(&self) -> &[MemoryHeap] {
+        &self.memory_heaps
+    }
+
+    pub fn is_supported(&self) -> bool {
+        self.unsupported_reasons.is_empty()
+    }
+
+    //why daxa cannot run on this adapter, if it cannot
+    pub fn unsupported_reasons(&self) -> &[&'static str] {
+        &self.unsupported_reasons
+    }
+}
+
+impl std::fmt::Display for PhysicalDevice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let properties = &self.properties;
+        let version = properties.api_version();
+        writeln!(
+            f,
+            "{} ({:?}, vendor 0x{:04x}, device 0x{:04x}, vulkan {}.{}.{})",
+            properties.name(),
+            properties.device_type(),
+            properties.vendor_id(),
+            properties.device_id(),
+            (version >> 22) & 0x7f,
+            (version >> 12) & 0x3ff,
+            version & 0xfff,
+        )?;
+        if self.features.is_empty() {
+            writeln!(f, "    features: none")?;
+        } else {
+            writeln!(f, "    features: {:?}", self.features)?;
+        }
+        for (index, heap) in self.memory_heaps.iter().enumerate() {
+            writeln!(
+                f,
+                "    heap {}: {} MiB{}",
+                index,
+                heap.size / (1024 * 1024),
+                if heap.device_local {
+                    " device local"
+                } else {
+                    ""
+                },
+            )?;
+        }
+        let limits = self.limits();
+        writeln!(
+            f,
+            "    limits: image 2d {}, push constants {} bytes, compute workgroup invocations {}",
+            limits.maxImageDimension2D,
+            limits.maxPushConstantsSize,
+            limits.maxComputeWorkGroupInvocations,
+        )?;
+        if self.is_supported() {
+            write!(f, "    supported")
+        } else {
+            write!(
+                f,
+                "    unsupported: {}",
+                self.unsupported_reasons.join(", ")
+            )
+        }
+    }
+}
+
+//scores a physical device, higher is better, None rejects the device outright
+pub type DeviceSelector = dyn FnMut(&PhysicalDeviceProperties) -> std::option::Option<i32> + Send;
+
+pub fn default_device_selector(properties: &PhysicalDeviceProperties) -> std::option::Option<i32> {
+    let score = unsafe { daxa_sys::daxa_default_device_score(properties.as_ptr()) };
+    (score >= 0).then_some(score)
+}
+
+thread_local! {
+    //daxa calls the selector synchronously from within device creation and passes no user data,
+    //so the closure for the device currently being created is parked here
+    static ACTIVE_SELECTOR: cell::RefCell<std::option::Option<sync::Arc<sync::Mutex<DeviceSelector>>>> =
+        cell::RefCell::new(None);
+}
+
+extern "C" fn selector_trampoline(properties: *const daxa_sys::VkPhysicalDeviceProperties) -> i32 {
+    const REJECTED: i32 = -1;
+    ACTIVE_SELECTOR.with(|active| {
+        let Some(selector) = active.borrow().clone() else {
+            return REJECTED;
+        };
+        let Some(properties) = (unsafe { PhysicalDeviceProperties::from_ptr(properties) }) else {
+            return REJECTED;
+        };
+        //unwinding into C is undefined, so a panicking selector just rejects the device
+        panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let mut selector = selector.lock().unwrap_or_else(|e| e.into_inner());
+            (&mut *selector)(properties)
+        }))
+        .ok()
+        .flatten()
+        .unwrap_or(REJECTED)
+    })
+}
 
-pub extern "C" fn default_device_selector(properties: *const VkPhysicalDeviceProperties) -> i32 {
-    unsafe { daxa_sys::daxa_default_device_score(properties) }
+pub(crate) struct SelectorGuard {
+    previous: std::option::Option<sync::Arc<sync::Mutex<DeviceSelector>>>,
 }
 
-pub struct DeviceInfo<'a> {
-    selector: DeviceSelector,
+impl Drop for SelectorGuard {
+    fn drop(&mut self) {
+        ACTIVE_SELECTOR.with(|active| *active.borrow_mut() = self.previous.take());
+    }
+}
+
+#[derive(Clone)]
+pub struct DeviceInfo {
+    selector: sync::Arc<sync::Mutex<DeviceSelector>>,
     flags: DeviceFlags,
     max_allowed_images: u32,
     max_allowed_buffers: u32,
@@ -40,412 +216,808 @@ pub struct DeviceInfo<'a> {
     name: String,
 }
 
-pub struct Device(daxa_sys::daxa_Device);
+impl Default for DeviceInfo {
+    fn default() -> Self {
+        Self {
+            selector: sync::Arc::new(sync::Mutex::new(default_device_selector)),
+            flags: DeviceFlags::BUFFER_DEVICE_ADDRESS_CAPTURE_REPLAY_BIT,
+            max_allowed_images: 10000,
+            max_allowed_buffers: 10000,
+            max_allowed_samplers: 400,
+            name: String::new(),
+        }
+    }
+}
 
-impl Device {
-    pub fn buffer_memory_requirements(&self, info: &[BufferInfo]) -> MemoryRequirements {
+impl DeviceInfo {
+    pub fn builder() -> DeviceInfoBuilder {
+        DeviceInfoBuilder::default()
+    }
+
+    pub fn flags(&self) -> DeviceFlags {
+        self.flags
+    }
+
+    pub fn max_allowed_images(&self) -> u32 {
+        self.max_allowed_images
+    }
+
+    pub fn max_allowed_buffers(&self) -> u32 {
+        self.max_allowed_buffers
+    }
+
+    pub fn max_allowed_samplers(&self) -> u32 {
+        self.max_allowed_samplers
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    //makes this info's selector the one the trampoline forwards to until the guard drops
+    pub(crate) fn bind_selector(&self) -> SelectorGuard {
+        let previous = ACTIVE_SELECTOR.with(|active| active.replace(Some(self.selector.clone())));
+        SelectorGuard { previous }
+    }
+}
+
+impl AsCInfo for DeviceInfo {
+    type C = daxa_sys::daxa_DeviceInfo;
+
+    fn as_c_info(&self) -> std::result::Result<CInfo<Self::C>, InvalidName> {
+        CInfo::new(&self.name, |name| daxa_sys::daxa_DeviceInfo {
+            selector: Some(selector_trampoline),
+            flags: self.flags.bits(),
+            max_allowed_images: self.max_allowed_images,
+            max_allowed_buffers: self.max_allowed_buffers,
+            max_allowed_samplers: self.max_allowed_samplers,
+            name,
+        })
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct DeviceInfoBuilder {
+    info: DeviceInfo,
+}
+
+impl DeviceInfoBuilder {
+    pub fn selector(
+        mut self,
+        selector: impl FnMut(&PhysicalDeviceProperties) -> std::option::Option<i32> + Send + 'static,
+    ) -> Self {
+        self.info.selector = sync::Arc::new(sync::Mutex::new(selector));
+        self
+    }
+
+    pub fn flags(mut self, flags: DeviceFlags) -> Self {
+        self.info.flags = flags;
+        self
+    }
+
+    pub fn max_allowed_images(mut self, count: u32) -> Self {
+        self.info.max_allowed_images = count;
+        self
+    }
+
+    pub fn max_allowed_buffers(mut self, count: u32) -> Self {
+        self.info.max_allowed_buffers = count;
+        self
+    }
+
+    pub fn max_allowed_samplers(mut self, count: u32) -> Self {
+        self.info.max_allowed_samplers = count;
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.info.name = name.into();
+        self
+    }
+
+    pub fn build(self) -> DeviceInfo {
+        self.info
+    }
+}
+
+#[derive(Debug)]
+pub enum DeviceCreateError {
+    //daxa reads names as c strings, so they cannot contain nul bytes
+    InvalidName,
+    MissingExtension,
+    NoSuitableDevice,
+    Daxa(DaxaError),
+}
+
+impl std::fmt::Display for DeviceCreateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for DeviceCreateError {}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    DeviceLost,
+    Daxa(DaxaError),
+}
+
+impl std::fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for SubmitError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresentStatus {
+    Optimal,
+    Suboptimal,
+}
+
+#[derive(Debug)]
+pub enum PresentError {
+    //the swapchain no longer matches its surface and has to be resized before the next present
+    OutOfDate,
+    //a headless swapchain was presented without acquiring an image first
+    NotAcquired,
+    DeviceLost,
+    Daxa(DaxaError),
+}
+
+impl std::fmt::Display for PresentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for PresentError {}
+
+//a dropped resource waiting for the next collect_garbage
+#[derive(Clone, Copy)]
+pub(crate) enum Zombie {
+    Buffer(BufferId),
+    Image(ImageId),
+    ImageView(ImageViewId),
+    Sampler(SamplerId),
+}
+
+struct DeviceInner {
+    handle: daxa_sys::daxa_Device,
+    info: DeviceInfo,
+    zombies: sync::Mutex<Vec<Zombie>>,
+    wait_semaphores: sync::OnceLock<daxa_sys::PFN_vkWaitSemaphores>,
+    cmd_pipeline_barrier2: sync::OnceLock<daxa_sys::PFN_vkCmdPipelineBarrier2>,
+    gpu_waiter: sync::OnceLock<GpuWaiter>,
+    instance: Instance,
+}
+
+impl DeviceInner {
+    unsafe fn destroy_zombies(&self) -> std::result::Result<(), DaxaError> {
+        let zombies = mem::take(&mut *self.zombies.lock().unwrap_or_else(|e| e.into_inner()));
+        let mut first_error = Ok(());
+        for zombie in zombies {
+            let result = match zombie {
+                Zombie::Buffer(id) => DaxaError::check(
+                    daxa_sys::daxa_dvc_destroy_buffer(self.handle, id),
+                    "destroy_buffer",
+                    "",
+                ),
+                Zombie::Image(id) => DaxaError::check(
+                    daxa_sys::daxa_dvc_destroy_image(self.handle, id),
+                    "destroy_image",
+                    "",
+                ),
+                Zombie::ImageView(id) => DaxaError::check(
+                    daxa_sys::daxa_dvc_destroy_image_view(self.handle, id),
+                    "destroy_image_view",
+                    "",
+                ),
+                Zombie::Sampler(id) => DaxaError::check(
+                    daxa_sys::daxa_dvc_destroy_sampler(self.handle, id),
+                    "destroy_sampler",
+                    "",
+                ),
+            };
+            //keep destroying the rest, one bad id should not leak everything after it
+            if first_error.is_ok() {
+                first_error = result;
+            }
+        }
+        first_error
+    }
+}
+
+impl Drop for DeviceInner {
+    fn drop(&mut self) {
+        //releases the waiter's wake semaphore while its device is still around
+        drop(self.gpu_waiter.take());
         unsafe {
-            mem::transmute::<MemoryRequirements>(daxa_sys::daxa_dvc_buffer_memory_requirements(
-                self.handle,
-                info.as_ptr().cast::<daxa_sys::daxa_BufferInfo>(),
+            //every resource holds a device clone, so all of them are zombies by now
+            daxa_sys::daxa_dvc_wait_idle(self.handle);
+            let _ = self.destroy_zombies();
+            daxa_sys::daxa_dvc_collect_garbage(self.handle);
+            daxa_sys::daxa_destroy_device(self.handle);
+        }
+    }
+}
+
+//daxa synchronizes device calls internally, so one device can be shared across threads
+unsafe impl Send for DeviceInner {}
+unsafe impl Sync for DeviceInner {}
+
+#[derive(Clone)]
+pub struct Device {
+    inner: sync::Arc<DeviceInner>,
+}
+
+impl Device {
+    pub(crate) fn from_raw(
+        handle: daxa_sys::daxa_Device,
+        info: DeviceInfo,
+        instance: Instance,
+    ) -> Self {
+        Self {
+            inner: sync::Arc::new(DeviceInner {
+                handle,
+                info,
+                zombies: sync::Mutex::new(vec![]),
+                wait_semaphores: sync::OnceLock::new(),
+                cmd_pipeline_barrier2: sync::OnceLock::new(),
+                gpu_waiter: sync::OnceLock::new(),
+                instance,
+            }),
+        }
+    }
+
+    pub(crate) fn vk_device(&self) -> daxa_sys::VkDevice {
+        unsafe { daxa_sys::daxa_dvc_get_vk_device(self.inner.handle) }
+    }
+
+    //daxa only waits on one semaphore at a time, waits over several go to vulkan directly
+    pub(crate) fn vk_wait_semaphores(&self) -> daxa_sys::PFN_vkWaitSemaphores {
+        *self.inner.wait_semaphores.get_or_init(|| unsafe {
+            mem::transmute::<_, daxa_sys::PFN_vkWaitSemaphores>(loader::get_device_proc_addr(
+                self.inner.instance.vk_instance(),
+                self.vk_device(),
+                b"vkWaitSemaphores\0".as_ptr().cast(),
+            ))
+        })
+    }
+
+    //daxa records barriers one at a time, batches of them go to vulkan directly
+    pub(crate) fn vk_cmd_pipeline_barrier2(&self) -> daxa_sys::PFN_vkCmdPipelineBarrier2 {
+        *self.inner.cmd_pipeline_barrier2.get_or_init(|| unsafe {
+            mem::transmute::<_, daxa_sys::PFN_vkCmdPipelineBarrier2>(loader::get_device_proc_addr(
+                self.inner.instance.vk_instance(),
+                self.vk_device(),
+                b"vkCmdPipelineBarrier2\0".as_ptr().cast(),
             ))
+        })
+    }
+
+    //a failed spawn is retried by the next future that needs the waiter
+    pub(crate) fn gpu_waiter(&self) -> std::result::Result<&GpuWaiter, DaxaError> {
+        if let Some(gpu_waiter) = self.inner.gpu_waiter.get() {
+            return Ok(gpu_waiter);
         }
+        //a waiter that loses the race is dropped, which closes its channel and ends its thread
+        let _ = self.inner.gpu_waiter.set(GpuWaiter::spawn(self)?);
+        Ok(self.inner.gpu_waiter.get().unwrap())
+    }
+
+    pub(crate) fn zombify(&self, zombie: Zombie) {
+        self.inner
+            .zombies
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(zombie);
     }
 
-    pub fn image_memory_requirements(&self, info: &[ImageInfo]) -> MemoryRequirements {
+    //the name plays no part in the requirements, so an unnamed copy is lowered and this cannot fail
+    pub fn buffer_memory_requirements(&self, info: &BufferInfo) -> MemoryRequirements {
+        let info = BufferInfo {
+            name: String::new(),
+            ..info.clone()
+        };
+        let c_info = info.as_c_info().unwrap();
         unsafe {
-            mem::transmute::<MemoryRequirements>(daxa_sys::daxa_dvc_image_memory_requirements(
-                self.handle,
-                info.as_ptr().cast::<daxa_sys::daxa_ImageInfo>(),
-            ))
+            mem::transmute::<daxa_sys::VkMemoryRequirements, MemoryRequirements>(
+                daxa_sys::daxa_dvc_buffer_memory_requirements(self.inner.handle, c_info.as_ptr()),
+            )
+        }
+    }
+
+    pub fn image_memory_requirements(&self, info: &ImageInfo) -> MemoryRequirements {
+        let info = ImageInfo {
+            name: String::new(),
+            ..info.clone()
+        };
+        let c_info = info.as_c_info().unwrap();
+        unsafe {
+            mem::transmute::<daxa_sys::VkMemoryRequirements, MemoryRequirements>(
+                daxa_sys::daxa_dvc_image_memory_requirements(self.inner.handle, c_info.as_ptr()),
+            )
         }
     }
 
     pub fn create_memory(
         &self,
-        info: &[MemoryBlockInfo],
-    ) -> std::result::Result<MemoryBlock, crate::types::Result> {
-        use crate::Result::*;
+        info: &MemoryBlockInfo,
+    ) -> std::result::Result<MemoryBlock, DaxaError> {
         unsafe {
             let mut out_memory_block = mem::zeroed();
 
             let c_result = daxa_sys::daxa_dvc_create_memory(
-                self.handle,
-                info.as_ptr().cast::<daxa_sys::daxa_MemoryBlockInfo>(),
+                self.inner.handle,
+                (info as *const MemoryBlockInfo).cast::<daxa_sys::daxa_MemoryBlockInfo>(),
                 &mut out_memory_block,
             );
 
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(out_memory_block),
-                _ => Err(c_result),
-            }
+            DaxaError::check(c_result, "create_memory", self.info().name())?;
+            Ok(out_memory_block)
         }
     }
 
-    pub fn create_buffer(
-        &self,
-        info: &[BufferInfo],
-    ) -> std::result::Result<Buffer, crate::types::Result> {
-        use crate::Result::*;
+    pub fn create_buffer(&self, info: &BufferInfo) -> std::result::Result<Buffer, DaxaError> {
+        let c_info = info
+            .as_c_info()
+            .map_err(|_| DaxaError::invalid_name("create_buffer", &info.name))?;
         unsafe {
             let mut handle = mem::zeroed();
 
-            let c_result = daxa_sys::daxa_dvc_create_buffer(
-                self.handle,
-                info.as_ptr().cast::<daxa_sys::daxa_BufferInfo>(),
-                &mut handle,
-            );
-
-            let buffer = Buffer {
-                handle,
-                device: self.clone(),
-            };
+            let c_result =
+                daxa_sys::daxa_dvc_create_buffer(self.inner.handle, c_info.as_ptr(), &mut handle);
 
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(buffer),
-                _ => Err(c_result),
-            }
+            DaxaError::check(c_result, "create_buffer", &info.name)?;
+            Ok(Buffer::from_raw(self.clone(), handle, info.clone()))
         }
     }
 
-    pub fn create_image(
-        &self,
-        info: &[ImageInfo],
-    ) -> std::result::Result<Image, crate::types::Result> {
-        use crate::types::Result;
-        use Result::*;
+    pub fn create_image(&self, info: &ImageInfo) -> std::result::Result<Image, DaxaError> {
+        let c_info = info
+            .as_c_info()
+            .map_err(|_| DaxaError::invalid_name("create_image", &info.name))?;
         unsafe {
             let mut handle = mem::zeroed();
 
-            let c_result = daxa_sys::daxa_dvc_create_image(
-                self.handle,
-                info.as_ptr().cast::<daxa_sys::daxa_ImageInfo>(),
-                &mut handle,
-            );
-
-            let image = Buffer {
-                handle,
-                device: self.clone(),
-            };
+            let c_result =
+                daxa_sys::daxa_dvc_create_image(self.inner.handle, c_info.as_ptr(), &mut handle);
 
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(image),
-                _ => Err(c_result),
-            }
+            DaxaError::check(c_result, "create_image", &info.name)?;
+            Ok(Image::from_raw(self.clone(), handle, info.clone()))
         }
     }
 
     pub fn create_image_view(
         &self,
-        info: &[ImageViewInfo],
-    ) -> std::result::Result<ImageView, crate::types::Result> {
-        use crate::types::Result;
-        use Result::*;
+        info: &ImageViewInfo,
+    ) -> std::result::Result<ImageView, DaxaError> {
+        let c_info = info
+            .as_c_info()
+            .map_err(|_| DaxaError::invalid_name("create_image_view", &info.name))?;
         unsafe {
             let mut handle = mem::zeroed();
 
             let c_result = daxa_sys::daxa_dvc_create_image_view(
-                self.handle,
-                info.as_ptr().cast::<daxa_sys::daxa_ImageViewInfo>(),
+                self.inner.handle,
+                c_info.as_ptr(),
                 &mut handle,
             );
 
-            let image_view = ImageView {
-                handle,
-                device: self.clone(),
-            };
-
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(image_view),
-                _ => Err(c_result),
-            }
+            DaxaError::check(c_result, "create_image_view", &info.name)?;
+            Ok(ImageView::from_raw(self.clone(), handle, info.clone()))
         }
     }
 
-    pub fn create_sampler(
-        &self,
-        info: &[SamplerInfo],
-    ) -> std::result::Result<Sampler, crate::types::Result> {
-        use crate::types::Result;
-        use Result::*;
+    pub fn create_sampler(&self, info: &SamplerInfo) -> std::result::Result<Sampler, DaxaError> {
+        let c_info = info
+            .as_c_info()
+            .map_err(|_| DaxaError::invalid_name("create_sampler", &info.name))?;
         unsafe {
             let mut handle = mem::zeroed();
 
-            let c_result = daxa_sys::daxa_dvc_create_sampler(
-                self.handle,
-                info.as_ptr().cast::<daxa_sys::daxa_SamplerInfo>(),
-                &mut handle,
-            );
+            let c_result =
+                daxa_sys::daxa_dvc_create_sampler(self.inner.handle, c_info.as_ptr(), &mut handle);
 
-            let sampler = Sampler {
-                handle,
-                device: self.clone(),
-            };
-
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(sampler),
-                _ => Err(c_result),
-            }
+            DaxaError::check(c_result, "create_sampler", &info.name)?;
+            Ok(Sampler::from_raw(self.clone(), handle, info.clone()))
         }
     }
 
     pub fn is_buffer_valid(&self, buffer: BufferId) -> bool {
-        unsafe { daxa_sys::daxa_dvc_is_buffer_valid(self.handle, buffer) }
+        unsafe { daxa_sys::daxa_dvc_is_buffer_valid(self.inner.handle, buffer) }
     }
 
     pub fn is_image_valid(&self, image: ImageId) -> bool {
-        unsafe { daxa_sys::daxa_dvc_is_image_valid(self.handle, image) }
+        unsafe { daxa_sys::daxa_dvc_is_image_valid(self.inner.handle, image) }
     }
 
     pub fn is_image_view_valid(&self, image_view: ImageViewId) -> bool {
-        unsafe { daxa_sys::daxa_dvc_is_image_view_valid(self.handle, image_view) }
+        unsafe { daxa_sys::daxa_dvc_is_image_view_valid(self.inner.handle, image_view) }
     }
 
-    pub fn is_sampler_valid(&self, sampler: SamplerId) -> bool {
-        unsafe { daxa_sys::daxa_dvc_is_sampler_valid(self.handle, sampler) }
+    //the image a view was created from, None once the view is destroyed
+    pub(crate) fn image_view_image(&self, image_view: ImageViewId) -> std::option::Option<ImageId> {
+        unsafe {
+            let mut info: daxa_sys::daxa_ImageViewInfo = mem::zeroed();
+            let c_result =
+                daxa_sys::daxa_dvc_info_image_view(self.inner.handle, image_view, &mut info);
+            (c_result == daxa_sys::daxa_Result_DAXA_RESULT_SUCCESS).then_some(info.image)
+        }
     }
 
-    pub fn create_raster_pipeline(
+    //the vulkan image behind an id and its format, None once the image is destroyed
+    pub(crate) fn vk_image(
         &self,
-        info: &crate::pipeline::RasterPipelineInfo,
-    ) -> std::result::Result<crate::pipeline::RasterPipeline, crate::types::Result> {
-        use crate::types::Result;
-        use Result::Success;
+        image: ImageId,
+    ) -> std::option::Option<(daxa_sys::VkImage, daxa_sys::VkFormat)> {
         unsafe {
-            let c_info = info.as_ptr().cast::<daxa_sys::daxa_RasterPipelineInfo>();
+            let mut info: daxa_sys::daxa_ImageInfo = mem::zeroed();
+            let mut vk_image: daxa_sys::VkImage = mem::zeroed();
+            let info_result = daxa_sys::daxa_dvc_info_image(self.inner.handle, image, &mut info);
+            let image_result =
+                daxa_sys::daxa_dvc_get_vk_image(self.inner.handle, image, &mut vk_image);
+            (info_result == daxa_sys::daxa_Result_DAXA_RESULT_SUCCESS
+                && image_result == daxa_sys::daxa_Result_DAXA_RESULT_SUCCESS)
+                .then_some((vk_image, info.format))
+        }
+    }
+
+    pub fn is_sampler_valid(&self, sampler: SamplerId) -> bool {
+        unsafe { daxa_sys::daxa_dvc_is_sampler_valid(self.inner.handle, sampler) }
+    }
 
-            let mut raster_pipeline = std::mem::zeroed();
+    pub fn create_raster_pipeline<P: Pod>(
+        &self,
+        info: &RasterPipelineInfo,
+    ) -> std::result::Result<RasterPipeline<P>, PipelineCreateError> {
+        info.check_shaders::<P>()?;
+        let c_info = info
+            .as_c_info(pipeline::push_constant_size::<P>(self)?)
+            .map_err(|_| {
+                PipelineCreateError::Daxa(DaxaError::invalid_name(
+                    "create_raster_pipeline",
+                    &info.name,
+                ))
+            })?;
+        unsafe {
+            let mut handle = mem::zeroed();
 
             let c_result = daxa_sys::daxa_dvc_create_raster_pipeline(
-                self.handle,
-                c_info,
-                &mut raster_pipeline,
+                self.inner.handle,
+                c_info.as_ptr(),
+                &mut handle,
             );
 
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(raster_pipeline),
-                error => Err(error),
-            }
+            DaxaError::check(c_result, "create_raster_pipeline", &info.name)
+                .map_err(PipelineCreateError::Daxa)?;
+            Ok(RasterPipeline::from_raw(self.clone(), handle))
         }
     }
 
     //compute
-    pub fn create_compute_pipeline(
+    pub fn create_compute_pipeline<P: Pod>(
         &self,
-        info: &crate::pipeline::ComputePipelineInfo,
-    ) -> std::result::Result<crate::pipeline::ComputePipeline, crate::types::Result> {
-        use crate::types::Result;
-        use Result::Success;
+        info: &ComputePipelineInfo,
+    ) -> std::result::Result<ComputePipeline<P>, PipelineCreateError> {
+        info.check_shader::<P>()?;
+        let c_info = info
+            .as_c_info(pipeline::push_constant_size::<P>(self)?)
+            .map_err(|_| {
+                PipelineCreateError::Daxa(DaxaError::invalid_name(
+                    "create_compute_pipeline",
+                    &info.name,
+                ))
+            })?;
         unsafe {
-            let c_info = info.as_ptr().cast::<daxa_sys::daxa_ComputePipelineInfo>();
-
-            let mut compute_pipeline = std::mem::zeroed();
+            let mut handle = mem::zeroed();
 
             let c_result = daxa_sys::daxa_dvc_create_compute_pipeline(
-                self.handle,
-                c_info,
-                &mut compute_pipeline,
+                self.inner.handle,
+                c_info.as_ptr(),
+                &mut handle,
             );
 
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(compute_pipeline),
-                error => Err(error),
-            }
+            DaxaError::check(c_result, "create_compute_pipeline", &info.name)
+                .map_err(PipelineCreateError::Daxa)?;
+            Ok(ComputePipeline::from_raw(self.clone(), handle))
         }
     }
 
     //swapchain
-    pub fn create_swapchain(
+    //safety: the window has to outlive the swapchain, raw window handles carry no lifetime to check that
+    pub unsafe fn create_swapchain(
         &self,
-        info: &crate::pipeline::SwapchainInfo,
-    ) -> std::result::Result<crate::pipeline::Swapchain, crate::types::Result> {
-        use crate::types::Result;
-        use Result::Success;
-        unsafe {
-            let c_info = info.as_ptr().cast::<daxa_sys::daxa_SwapchainInfo>();
-
-            let mut swapchain = std::mem::zeroed();
-
-            let c_result = daxa_sys::daxa_dvc_create_swapchain(self.handle, c_info, &mut swapchain);
-
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(swapchain),
-                error => Err(error),
-            }
+        window: &(impl HasRawWindowHandle + HasRawDisplayHandle),
+        info: &SwapchainInfo,
+    ) -> std::result::Result<Swapchain, SwapchainCreateError> {
+        let native_window = NativeWindow::new(window)?;
+        let formats = surface_formats(
+            window,
+            self.inner.instance.vk_instance(),
+            daxa_sys::daxa_dvc_get_vk_physical_device(self.inner.handle),
+        )
+        .map_err(SwapchainCreateError::Daxa)?;
+        //checked before daxa creates anything, daxa picks a format even when every one is rejected
+        if formats.is_some_and(|formats| info.select_format(formats).is_none()) {
+            return Err(SwapchainCreateError::NoSuitableFormat);
+        }
+        let selector = info.bind_format_selector();
+        let c_info = info.as_c_info(native_window).map_err(|_| {
+            SwapchainCreateError::Daxa(DaxaError::invalid_name("create_swapchain", info.name()))
+        })?;
+
+        let mut handle = mem::zeroed();
+
+        let c_result =
+            daxa_sys::daxa_dvc_create_swapchain(self.inner.handle, c_info.as_ptr(), &mut handle);
+
+        DaxaError::check(c_result, "create_swapchain", self.info().name())
+            .map_err(SwapchainCreateError::Daxa)?;
+        let swapchain = Swapchain::from_raw(self.clone(), handle, info.clone());
+        //windows whose surface could not be queried up front are only checked once daxa is done,
+        //dropping the swapchain then destroys the one daxa created with a rejected format
+        if !selector.accepted_any() {
+            return Err(SwapchainCreateError::NoSuitableFormat);
         }
+        Ok(swapchain)
+    }
+
+    //renders into plain images instead of a surface, on_present receives every frame read back
+    //to host memory, so frame loops and screenshot tests run without a window system
+    //a frame is handed over by the first acquire or present after its copy finished, at the latest when
+    //its image is acquired again or the swapchain is dropped, the callback runs without swapchain locks
+    pub fn create_headless_swapchain(
+        &self,
+        extent: Extent2D,
+        info: &SwapchainInfo,
+        on_present: std::option::Option<Box<HeadlessPresentCallback>>,
+    ) -> std::result::Result<Swapchain, SwapchainCreateError> {
+        let headless = HeadlessSwapchain::new(self.clone(), extent, info.clone(), on_present)?;
+        Ok(Swapchain::headless(headless))
     }
 
     //command recorder
     pub fn create_command_recorder(
         &self,
-        info: &crate::pipeline::CommandRecorderInfo,
-    ) -> std::result::Result<crate::pipeline::CommandRecorder, crate::types::Result> {
-        use crate::types::Result;
-        use Result::Success;
+        info: &CommandRecorderInfo,
+    ) -> std::result::Result<CommandRecorder, DaxaError> {
+        let c_info = info
+            .as_c_info()
+            .map_err(|_| DaxaError::invalid_name("create_command_recorder", &info.name))?;
         unsafe {
-            let c_info = info.as_ptr().cast::<daxa_sys::daxa_CommandRecorderInfo>();
-
-            let mut command_recorder = std::mem::zeroed();
+            let mut handle = mem::zeroed();
 
             let c_result = daxa_sys::daxa_dvc_create_command_recorder(
-                self.handle,
-                c_info,
-                &mut command_recorder,
+                self.inner.handle,
+                c_info.as_ptr(),
+                &mut handle,
             );
 
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(command_recorder),
-                error => Err(error),
-            }
+            DaxaError::check(c_result, "create_command_recorder", &info.name)?;
+            Ok(CommandRecorder::from_raw(
+                self.clone(),
+                handle,
+                info.clone(),
+            ))
         }
     }
 
     //binary semaphore
     pub fn create_binary_semaphore(
         &self,
-        info: &crate::pipeline::BinarySemaphoreInfo,
-    ) -> std::result::Result<crate::pipeline::BinarySemaphore, crate::types::Result> {
-        use crate::types::Result;
-        use Result::Success;
+        info: &BinarySemaphoreInfo,
+    ) -> std::result::Result<BinarySemaphore, DaxaError> {
+        let c_info = info
+            .as_c_info()
+            .map_err(|_| DaxaError::invalid_name("create_binary_semaphore", &info.name))?;
         unsafe {
-            let c_info = info.as_ptr().cast::<daxa_sys::daxa_BinarySemaphoreInfo>();
-
-            let mut binary_semaphore = std::mem::zeroed();
+            let mut handle = mem::zeroed();
 
             let c_result = daxa_sys::daxa_dvc_create_binary_semaphore(
-                self.handle,
-                c_info,
-                &mut binary_semaphore,
+                self.inner.handle,
+                c_info.as_ptr(),
+                &mut handle,
             );
 
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(binary_semaphore),
-                error => Err(error),
-            }
+            DaxaError::check(c_result, "create_binary_semaphore", &info.name)?;
+            Ok(BinarySemaphore::from_raw(
+                self.clone(),
+                handle,
+                info.clone(),
+            ))
         }
     }
 
     //timeline semaphore
     pub fn create_timeline_semaphore(
         &self,
-        info: &crate::pipeline::TimelineSemaphoreInfo,
-    ) -> std::result::Result<crate::pipeline::TimelineSemaphore, crate::types::Result> {
-        use crate::types::Result;
-        use Result::Success;
+        info: &TimelineSemaphoreInfo,
+    ) -> std::result::Result<TimelineSemaphore, DaxaError> {
+        let c_info = info
+            .as_c_info()
+            .map_err(|_| DaxaError::invalid_name("create_timeline_semaphore", &info.name))?;
         unsafe {
-            let c_info = info.as_ptr().cast::<daxa_sys::daxa_TimelineSemaphoreInfo>();
-
-            let mut timeline_semaphore = std::mem::zeroed();
+            let mut handle = mem::zeroed();
 
             let c_result = daxa_sys::daxa_dvc_create_timeline_semaphore(
-                self.handle,
-                c_info,
-                &mut timeline_semaphore,
+                self.inner.handle,
+                c_info.as_ptr(),
+                &mut handle,
             );
 
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(timeline_semaphore),
-                error => Err(error),
-            }
+            DaxaError::check(c_result, "create_timeline_semaphore", &info.name)?;
+            Ok(TimelineSemaphore::from_raw(
+                self.clone(),
+                handle,
+                info.clone(),
+            ))
         }
     }
 
     //event
-    pub fn create_event(
-        &self,
-        info: &crate::pipeline::EventInfo,
-    ) -> std::result::Result<crate::pipeline::Event, crate::types::Result> {
-        use crate::types::Result;
-        use Result::Success;
+    pub fn create_event(&self, info: &EventInfo) -> std::result::Result<Event, DaxaError> {
+        let c_info = info
+            .as_c_info()
+            .map_err(|_| DaxaError::invalid_name("create_event", &info.name))?;
         unsafe {
-            let c_info = info.as_ptr().cast::<daxa_sys::daxa_EventInfo>();
-
-            let mut event = std::mem::zeroed();
+            let mut handle = mem::zeroed();
 
-            let c_result = daxa_sys::daxa_dvc_create_event(self.handle, c_info, &mut event);
+            let c_result =
+                daxa_sys::daxa_dvc_create_event(self.inner.handle, c_info.as_ptr(), &mut handle);
 
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(event),
-                error => Err(error),
-            }
+            DaxaError::check(c_result, "create_event", &info.name)?;
+            Ok(Event::from_raw(self.clone(), handle, info.clone()))
         }
     }
 
     //timeline query_pool
     pub fn create_timeline_query_pool(
         &self,
-        info: &crate::pipeline::TimelineQueryInfoInfo,
-    ) -> std::result::Result<crate::pipeline::TimelineQueryInfo, crate::types::Result> {
-        use crate::types::Result;
-        use Result::Success;
+        info: &TimelineQueryPoolInfo,
+    ) -> std::result::Result<TimelineQueryPool, DaxaError> {
+        let c_info = info
+            .as_c_info()
+            .map_err(|_| DaxaError::invalid_name("create_timeline_query_pool", &info.name))?;
         unsafe {
-            let c_info = info.as_ptr().cast::<daxa_sys::daxa_TimelineQueryInfoInfo>();
-
-            let mut timeline_query_pool = std::mem::zeroed();
+            let mut handle = mem::zeroed();
 
             let c_result = daxa_sys::daxa_dvc_create_timeline_query_pool(
-                self.handle,
-                c_info,
-                &mut timeline_query_pool,
+                self.inner.handle,
+                c_info.as_ptr(),
+                &mut handle,
             );
 
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(timeline_query_pool),
-                error => Err(error),
-            }
+            DaxaError::check(c_result, "create_timeline_query_pool", &info.name)?;
+            Ok(TimelineQueryPool::from_raw(
+                self.clone(),
+                handle,
+                info.clone(),
+            ))
         }
     }
 
     pub fn buffer_device_address(&self, buffer: BufferId) -> BufferDeviceAddress {
         unsafe {
             let mut address = mem::zeroed();
-            daxa_sys::daxa_dvc_buffer_device_address(self.handle, buffer, &mut address);
+            daxa_sys::daxa_dvc_buffer_device_address(self.inner.handle, buffer, &mut address);
             address
         }
     }
 
-    pub fn buffer_host_address(&self, buffer: BufferId) -> *mut () {
+    //null for buffers that are not host visible, use Buffer::map for safe access
+    pub(crate) fn buffer_host_address(
+        &self,
+        buffer: BufferId,
+    ) -> std::result::Result<*mut ffi::c_void, DaxaError> {
         unsafe {
-            let mut address = mem::zeroed();
-            daxa_sys::daxa_dvc_buffer_device_address(self.handle, buffer, &mut address as _ as _);
-            address
+            let mut address = std::ptr::null_mut();
+            let c_result =
+                daxa_sys::daxa_dvc_buffer_host_address(self.inner.handle, buffer, &mut address);
+
+            DaxaError::check(c_result, "buffer_host_address", self.info().name())?;
+            Ok(address.cast())
         }
     }
 
     pub fn info(&self) -> &DeviceInfo {
-        unsafe { daxa_sys::daxa_dvc_info(self.handle).as_ref().unwrap() }
+        &self.inner.info
     }
 
-    //TODO submit
-    //TODO present
-
-    pub fn wait_idle(&self) -> std::result::Result<(), crate::types::Result> {
-        use crate::types::Result;
-        use Result::Success;
+    pub fn properties(&self) -> &PhysicalDeviceProperties {
         unsafe {
-            match mem::transmute::<Result>(daxa_sys::daxa_dvc_wait_idle(self.handle)) {
-                Success => Ok(()),
-                error => Err(error),
-            }
+            PhysicalDeviceProperties::from_ptr(daxa_sys::daxa_dvc_properties(self.inner.handle))
+                .expect("daxa device without properties")
+        }
+    }
+
+    pub fn submit(&self, info: &CommandSubmitInfo) -> std::result::Result<(), SubmitError> {
+        let handles = info.handles();
+        let c_info = info.as_c(&handles);
+        let c_result = unsafe {
+            daxa_sys::daxa_dvc_submit(
+                self.inner.handle,
+                (&c_info as *const CCommandSubmitInfo).cast(),
+            )
+        };
+
+        match Result::try_from(c_result) {
+            Ok(Result::Success) => Ok(()),
+            Ok(Result::ErrorDeviceLost) => Err(SubmitError::DeviceLost),
+            _ => Err(SubmitError::Daxa(DaxaError::new(
+                c_result,
+                "submit",
+                self.info().name(),
+            ))),
+        }
+    }
+
+    //like submit, also signals the given timeline value and resolves the future once the gpu reached it
+    pub fn submit_with_future(
+        &self,
+        info: &CommandSubmitInfo,
+        signal: (TimelineSemaphore, u64),
+    ) -> std::result::Result<GpuFuture, SubmitError> {
+        let signal_timeline_semaphores = info
+            .signal_timeline_semaphores
+            .iter()
+            .cloned()
+            .chain([signal.clone()])
+            .collect::<Vec<_>>();
+        self.submit(&CommandSubmitInfo {
+            signal_timeline_semaphores: &signal_timeline_semaphores,
+            ..*info
+        })?;
+        let (semaphore, value) = signal;
+        Ok(GpuFuture::new(semaphore, value))
+    }
+
+    //a suboptimal swapchain still presents, it is reported so the caller can resize at its leisure
+    pub fn present(&self, info: &PresentInfo) -> std::result::Result<PresentStatus, PresentError> {
+        let Some(swapchain) = info.swapchain.handle() else {
+            let headless = info.swapchain.as_headless().unwrap();
+            return headless.present(info.wait_binary_semaphores);
+        };
+        let handles = info.handles();
+        let c_info = info.as_c(&handles, swapchain);
+        let c_result = unsafe {
+            daxa_sys::daxa_dvc_present(self.inner.handle, (&c_info as *const CPresentInfo).cast())
+        };
+
+        match Result::try_from(c_result) {
+            Ok(Result::Success) => Ok(PresentStatus::Optimal),
+            Ok(Result::SuboptimalKhr) => Ok(PresentStatus::Suboptimal),
+            Ok(Result::ErrorOutOfDateKhr) => Err(PresentError::OutOfDate),
+            Ok(Result::ErrorDeviceLost) => Err(PresentError::DeviceLost),
+            _ => Err(PresentError::Daxa(DaxaError::new(
+                c_result,
+                "present",
+                self.info().name(),
+            ))),
         }
     }
 
-    pub fn collect_garbage(&self) -> std::result::Result<(), crate::types::Result> {
-        use crate::types::Result;
-        use Result::Success;
+    pub fn wait_idle(&self) -> std::result::Result<(), DaxaError> {
         unsafe {
-            match mem::transmute::<Result>(daxa_sys::daxa_dvc_collect_garbage(self.handle)) {
-                Success => Ok(()),
-                error => Err(error),
-            }
+            DaxaError::check(
+                daxa_sys::daxa_dvc_wait_idle(self.inner.handle),
+                "wait_idle",
+                self.info().name(),
+            )
         }
     }
-}
 
-impl Drop for Device {
-    fn drop(&mut self) {
+    //destroys every resource dropped since the last call, once the gpu is done with it
+    pub fn collect_garbage(&self) -> std::result::Result<(), DaxaError> {
         unsafe {
-            daxa_sys::daxa_destroy_device(self.handle);
+            //daxa defers the actual free until pending submissions complete
+            let destroyed = self.inner.destroy_zombies();
+            DaxaError::check(
+                daxa_sys::daxa_dvc_collect_garbage(self.inner.handle),
+                "collect_garbage",
+                self.info().name(),
+            )?;
+            destroyed
         }
     }
 }
@@ -0,0 +1,119 @@
+use std::sync::atomic;
+use std::time;
+
+use crate::device::Device;
+use crate::sync::{TimelineSemaphore, TimelineSemaphoreInfo, WaitError};
+use crate::types::*;
+
+//tells timelines apart, so slots of one timeline can not be indexed with frames of another
+static NEXT_TIMELINE_ID: atomic::AtomicU64 = atomic::AtomicU64::new(0);
+
+//paces the cpu against the gpu, frame n only begins once frame n - frames_in_flight finished
+pub struct FrameTimeline {
+    id: u64,
+    semaphore: TimelineSemaphore,
+    frames_in_flight: usize,
+    frame_number: u64,
+}
+
+impl FrameTimeline {
+    pub fn new(
+        device: &Device,
+        frames_in_flight: usize,
+        name: impl Into<String>,
+    ) -> std::result::Result<Self, DaxaError> {
+        assert!(
+            frames_in_flight > 0,
+            "a frame timeline needs at least one frame in flight"
+        );
+        let semaphore = device.create_timeline_semaphore(&TimelineSemaphoreInfo {
+            initial_value: 0,
+            name: name.into(),
+        })?;
+        Ok(Self {
+            id: NEXT_TIMELINE_ID.fetch_add(1, atomic::Ordering::Relaxed),
+            semaphore,
+            frames_in_flight,
+            frame_number: 0,
+        })
+    }
+
+    pub fn frames_in_flight(&self) -> usize {
+        self.frames_in_flight
+    }
+
+    pub fn semaphore(&self) -> &TimelineSemaphore {
+        &self.semaphore
+    }
+
+    //every begun frame has to signal its value, or the frame reusing its slot waits forever
+    pub fn begin_frame(&mut self) -> std::result::Result<Frame<'_>, WaitError> {
+        let number = self.frame_number + 1;
+        let reused = number.saturating_sub(self.frames_in_flight as u64);
+        self.semaphore.wait_for_value(reused, time::Duration::MAX)?;
+        self.frame_number = number;
+        Ok(Frame {
+            timeline: self,
+            number,
+        })
+    }
+
+    pub fn per_frame<T>(&self, init: impl FnMut(usize) -> T) -> PerFrame<T> {
+        PerFrame {
+            timeline_id: self.id,
+            slots: (0..self.frames_in_flight).map(init).collect(),
+        }
+    }
+}
+
+//borrows the timeline, so no later frame can begin while this one is still being recorded
+pub struct Frame<'a> {
+    timeline: &'a FrameTimeline,
+    number: u64,
+}
+
+impl Frame<'_> {
+    //counts from 1, this is also the value the frame signals
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    pub fn index(&self) -> usize {
+        (self.number % self.timeline.frames_in_flight as u64) as usize
+    }
+
+    //goes into signal_timeline_semaphores of the last submit of the frame
+    pub fn signal(&self) -> (TimelineSemaphore, u64) {
+        (self.timeline.semaphore.clone(), self.number)
+    }
+}
+
+//one slot per frame in flight, only reachable through the frame that currently owns it
+pub struct PerFrame<T> {
+    timeline_id: u64,
+    slots: Vec<T>,
+}
+
+impl<T> PerFrame<T> {
+    pub fn get(&self, frame: &Frame) -> &T {
+        &self.slots[self.slot(frame)]
+    }
+
+    pub fn get_mut(&mut self, frame: &Frame) -> &mut T {
+        let slot = self.slot(frame);
+        &mut self.slots[slot]
+    }
+
+    //every slot at once, for teardown after the device went idle
+    pub fn into_inner(self) -> Vec<T> {
+        self.slots
+    }
+
+    fn slot(&self, frame: &Frame) -> usize {
+        assert_eq!(
+            self.timeline_id, frame.timeline.id,
+            "per frame slots indexed with a frame of another timeline"
+        );
+        frame.index()
+    }
+}
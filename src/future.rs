@@ -0,0 +1,258 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{self, mpsc};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time;
+
+use crate::device::Device;
+use crate::sync::{TimelineSemaphore, TimelineSemaphoreInfo, WaitError};
+use crate::types::DaxaError;
+
+struct FutureState {
+    result: Option<Result<(), WaitError>>,
+    waker: Option<Waker>,
+}
+
+struct PendingFuture {
+    semaphore: TimelineSemaphore,
+    value: u64,
+    state: sync::Arc<sync::Mutex<FutureState>>,
+}
+
+impl PendingFuture {
+    fn resolve(self, result: Result<(), WaitError>) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.result = Some(result);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+struct WaiterQueue {
+    sender: mpsc::Sender<PendingFuture>,
+    //the last value the wake semaphore was signalled with
+    wake_value: u64,
+}
+
+//one per device, spawned on the first future that is not already complete when polled
+pub(crate) struct GpuWaiter {
+    queue: sync::Mutex<WaiterQueue>,
+    //raw, a wrapper would hold the device that owns this waiter and keep it alive forever
+    wake: daxa_sys::daxa_TimelineSemaphore,
+    wake_info: TimelineSemaphoreInfo,
+}
+
+//the raw semaphore is only signalled and shared, which daxa synchronizes internally
+unsafe impl Send for GpuWaiter {}
+unsafe impl Sync for GpuWaiter {}
+
+impl GpuWaiter {
+    pub(crate) fn spawn(device: &Device) -> Result<Self, DaxaError> {
+        let wake_info = TimelineSemaphoreInfo {
+            initial_value: 0,
+            name: format!("{} gpu waiter wake", device.info().name()),
+        };
+        let wake = device.create_timeline_semaphore(&wake_info)?;
+        let (sender, receiver) = mpsc::channel();
+        let name = format!("{} gpu waiter", device.info().name());
+        //the os refusing a thread is reported like daxa failing to set something up
+        thread::Builder::new()
+            .name(name.clone())
+            .spawn(move || Self::run(receiver))
+            .map_err(|_| {
+                DaxaError::new(
+                    crate::types::Result::ErrorInitializationFailed as i32,
+                    "spawn",
+                    name,
+                )
+            })?;
+        Ok(Self {
+            queue: sync::Mutex::new(WaiterQueue {
+                sender,
+                wake_value: 0,
+            }),
+            wake: unsafe {
+                daxa_sys::daxa_timeline_semaphore_inc_refcnt(wake.handle());
+                wake.handle()
+            },
+            wake_info,
+        })
+    }
+
+    fn wake_semaphore(&self, device: &Device) -> TimelineSemaphore {
+        unsafe { daxa_sys::daxa_timeline_semaphore_inc_refcnt(self.wake) };
+        TimelineSemaphore::from_raw(device.clone(), self.wake, self.wake_info.clone())
+    }
+
+    fn register(&self, pending: PendingFuture) {
+        let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        //the waiter only stops once the device and with it the sender is gone
+        let _ = queue.sender.send(pending);
+        Self::signal_wake(self.wake, &mut queue);
+    }
+
+    //interrupts the waiter's wait on the gpu, so it picks up new and dropped futures
+    fn wake(&self) {
+        let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        Self::signal_wake(self.wake, &mut queue);
+    }
+
+    //sent futures are visible to the waiter before the value it waits for is signalled
+    fn signal_wake(wake: daxa_sys::daxa_TimelineSemaphore, queue: &mut WaiterQueue) {
+        queue.wake_value += 1;
+        //a failed signal leaves the device lost, which the waiter's own wait reports
+        let _ = unsafe { daxa_sys::daxa_timeline_semaphore_set_value(wake, queue.wake_value) };
+    }
+
+    //pending futures keep their device alive, so the channel only closes once nothing is pending
+    fn run(receiver: mpsc::Receiver<PendingFuture>) {
+        let mut pending = Vec::<PendingFuture>::new();
+        loop {
+            if pending.is_empty() {
+                match receiver.recv() {
+                    Ok(future) => pending.push(future),
+                    Err(mpsc::RecvError) => return,
+                }
+            }
+            let device = pending[0].semaphore.device().clone();
+            //read before draining, a future sent after the drain signals past this value
+            let woken = device.gpu_waiter().and_then(|waiter| {
+                let wake = waiter.wake_semaphore(&device);
+                let value = wake.value()?;
+                Ok((wake, value))
+            });
+            let (wake, woken_at) = match woken {
+                Ok(value) => value,
+                Err(error) => {
+                    for future in pending.drain(..) {
+                        future.resolve(Err(WaitError::Daxa(error.clone())));
+                    }
+                    continue;
+                }
+            };
+            pending.extend(receiver.try_iter());
+            //nobody is left to poll the futures that were dropped
+            pending.retain(|future| sync::Arc::strong_count(&future.state) > 1);
+            if pending.is_empty() {
+                continue;
+            }
+
+            let mut waits = pending
+                .iter()
+                .map(|future| (future.semaphore.clone(), future.value))
+                .collect::<Vec<_>>();
+            waits.push((wake, woken_at + 1));
+            if let Err(error) = TimelineSemaphore::wait_any(&waits, time::Duration::MAX) {
+                for future in pending.drain(..) {
+                    future.resolve(Err(error.clone()));
+                }
+                continue;
+            }
+
+            let mut still_pending = Vec::with_capacity(pending.len());
+            for future in pending.drain(..) {
+                match future.semaphore.value() {
+                    Ok(value) if value < future.value => still_pending.push(future),
+                    Ok(_) => future.resolve(Ok(())),
+                    Err(error) => future.resolve(Err(WaitError::Daxa(error))),
+                }
+            }
+            pending = still_pending;
+        }
+    }
+}
+
+impl Drop for GpuWaiter {
+    fn drop(&mut self) {
+        unsafe { daxa_sys::daxa_timeline_semaphore_dec_refcnt(self.wake) };
+    }
+}
+
+//resolves once the semaphore reaches the value, without blocking the executor
+pub struct GpuFuture {
+    semaphore: TimelineSemaphore,
+    value: u64,
+    state: Option<sync::Arc<sync::Mutex<FutureState>>>,
+}
+
+impl GpuFuture {
+    pub fn new(semaphore: TimelineSemaphore, value: u64) -> Self {
+        Self {
+            semaphore,
+            value,
+            state: None,
+        }
+    }
+
+    pub fn semaphore(&self) -> &TimelineSemaphore {
+        &self.semaphore
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+impl Future for GpuFuture {
+    type Output = Result<(), WaitError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if let Some(state) = &this.state {
+            let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(result) = state.result.take() else {
+                state.waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            };
+            drop(state);
+            this.state = None;
+            return Poll::Ready(result);
+        }
+
+        //already finished work never touches the waiter thread
+        match this.semaphore.value() {
+            Ok(value) if value >= this.value => return Poll::Ready(Ok(())),
+            Ok(_) => {}
+            Err(error) => return Poll::Ready(Err(WaitError::Daxa(error))),
+        }
+        let waiter = match this.semaphore.device().gpu_waiter() {
+            Ok(waiter) => waiter,
+            Err(error) => return Poll::Ready(Err(WaitError::Daxa(error))),
+        };
+        let state = sync::Arc::new(sync::Mutex::new(FutureState {
+            result: None,
+            waker: Some(cx.waker().clone()),
+        }));
+        this.state = Some(state.clone());
+        waiter.register(PendingFuture {
+            semaphore: this.semaphore.clone(),
+            value: this.value,
+            state,
+        });
+        Poll::Pending
+    }
+}
+
+//an unresolved future would otherwise stay with the waiter until the gpu reaches its value
+impl Drop for GpuFuture {
+    fn drop(&mut self) {
+        let Some(state) = self.state.take() else {
+            return;
+        };
+        let resolved = state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .result
+            .is_some();
+        //the waiter prunes futures it holds the only reference to
+        drop(state);
+        if !resolved {
+            //registered futures always have a waiter, so this never spawns one
+            if let Ok(waiter) = self.semaphore.device().gpu_waiter() {
+                waiter.wake();
+            }
+        }
+    }
+}
@@ -1,11 +1,36 @@
+use std::ffi;
 use std::mem;
+use std::ptr;
+use std::sync;
 
+use crate::device::{
+    Device, DeviceCreateError, DeviceFlags, DeviceInfo, MemoryHeap, PhysicalDevice,
+};
+use crate::loader;
 use crate::types::*;
 use bitflags::bitflags;
 use daxa_sys;
 
+struct InstanceInner {
+    handle: daxa_sys::daxa_Instance,
+}
+
+impl Drop for InstanceInner {
+    fn drop(&mut self) {
+        unsafe {
+            daxa_sys::daxa_destroy_instance(self.handle);
+        }
+    }
+}
+
+//daxa instances are only read after creation
+unsafe impl Send for InstanceInner {}
+unsafe impl Sync for InstanceInner {}
+
+//devices hold a clone, so the instance outlives everything created from it
+#[derive(Clone)]
 pub struct Instance {
-    instance: daxa_sys::daxa_Instance,
+    inner: sync::Arc<InstanceInner>,
 }
 
 bitflags! {
@@ -23,52 +48,337 @@ pub struct InstanceInfo {
 
 #[derive(Debug)]
 pub enum InstanceCreateError {
+    //only with the dynamic-loader feature, no vulkan loader could be opened
+    LoaderNotFound,
     MissingExtension,
-    Unknown,
+    Daxa(DaxaError),
 }
 
 impl std::fmt::Display for InstanceCreateError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{:?}", &self)
+        write!(f, "{:?}", &self)
     }
 }
 impl std::error::Error for InstanceCreateError {}
 
+assert_c_layout!(InstanceInfo, daxa_sys::daxa_InstanceInfo);
+
+#[derive(Debug)]
+pub enum PhysicalDeviceEnumerateError {
+    MissingFunction(&'static str),
+    Vulkan(DaxaError),
+}
+
+impl std::fmt::Display for PhysicalDeviceEnumerateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for PhysicalDeviceEnumerateError {}
+
+//resolves a vulkan instance function into its typed pointer, bailing out of the caller if the loader lacks it
+macro_rules! load_instance_fn {
+    ($instance:expr, $name:ident: $pfn:ident) => {
+        mem::transmute::<_, daxa_sys::$pfn>(loader::get_instance_proc_addr(
+            $instance,
+            concat!(stringify!($name), "\0").as_ptr().cast(),
+        ))
+        .ok_or(PhysicalDeviceEnumerateError::MissingFunction(stringify!(
+            $name
+        )))?
+    };
+}
+
+fn check_vk(
+    result: daxa_sys::VkResult,
+    operation: &'static str,
+) -> std::result::Result<(), PhysicalDeviceEnumerateError> {
+    //daxa results mirror vulkan's, and positive codes such as VK_INCOMPLETE are not failures
+    if result < 0 {
+        Err(PhysicalDeviceEnumerateError::Vulkan(DaxaError::new(
+            result, operation, "",
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+const fn vk_version(major: u32, minor: u32) -> u32 {
+    (major << 22) | (minor << 12)
+}
+
 impl Instance {
+    pub(crate) fn vk_instance(&self) -> daxa_sys::VkInstance {
+        unsafe { daxa_sys::daxa_instance_get_vk_instance(self.inner.handle) }
+    }
+
     pub fn new(info: &InstanceInfo) -> std::result::Result<Self, InstanceCreateError> {
         use crate::types::Result;
         use Result::*;
+        if loader::instance_proc_addr().is_none() {
+            return Err(InstanceCreateError::LoaderNotFound);
+        }
         unsafe {
-            let c_info = info.as_ptr().cast::<daxa_sys::daxa_InstanceInfo>();
+            let c_info = (info as *const InstanceInfo).cast::<daxa_sys::daxa_InstanceInfo>();
 
             let mut c_instance = std::mem::zeroed();
 
             let c_result = daxa_sys::daxa_create_instance(c_info, &mut c_instance);
 
-            match mem::transmute::<Result>(c_result) {
-                Success => Ok(Instance {
-                    instance: c_instance,
+            match Result::try_from(c_result) {
+                Ok(Success) => Ok(Instance {
+                    inner: sync::Arc::new(InstanceInner { handle: c_instance }),
                 }),
-                MissingExtension => Err(InstanceCreateError::MissingExtension),
-                _ => Err(InstanceCreateError::Unknown),
+                Ok(MissingExtension) => Err(InstanceCreateError::MissingExtension),
+                _ => Err(InstanceCreateError::Daxa(DaxaError::new(
+                    c_result,
+                    "create_instance",
+                    "",
+                ))),
+            }
+        }
+    }
+
+    pub fn create_device(
+        &self,
+        info: &DeviceInfo,
+    ) -> std::result::Result<Device, DeviceCreateError> {
+        use crate::types::Result;
+        use Result::*;
+        let c_info = info
+            .as_c_info()
+            .map_err(|_| DeviceCreateError::InvalidName)?;
+        let _selector = info.bind_selector();
+        unsafe {
+            let mut c_device = std::mem::zeroed();
+
+            let c_result = daxa_sys::daxa_instance_create_device(
+                self.inner.handle,
+                c_info.as_ptr(),
+                &mut c_device,
+            );
+
+            match Result::try_from(c_result) {
+                Ok(Success) => Ok(Device::from_raw(c_device, info.clone(), self.clone())),
+                Ok(MissingExtension) => Err(DeviceCreateError::MissingExtension),
+                Ok(NoSuitableDeviceFound) => Err(DeviceCreateError::NoSuitableDevice),
+                _ => Err(DeviceCreateError::Daxa(DaxaError::new(
+                    c_result,
+                    "create_device",
+                    info.name(),
+                ))),
             }
         }
     }
 
+    //lists every adapter the loader can see, including the ones daxa cannot run on
+    pub fn enumerate_physical_devices(
+        &self,
+    ) -> std::result::Result<Vec<PhysicalDevice>, PhysicalDeviceEnumerateError> {
+        unsafe {
+            let vk_instance = daxa_sys::daxa_instance_get_vk_instance(self.inner.handle);
+
+            let enumerate_physical_devices = load_instance_fn!(
+                vk_instance,
+                vkEnumeratePhysicalDevices: PFN_vkEnumeratePhysicalDevices
+            );
+            let get_properties = load_instance_fn!(
+                vk_instance,
+                vkGetPhysicalDeviceProperties: PFN_vkGetPhysicalDeviceProperties
+            );
+            let get_features = load_instance_fn!(
+                vk_instance,
+                vkGetPhysicalDeviceFeatures2: PFN_vkGetPhysicalDeviceFeatures2
+            );
+            let get_memory_properties = load_instance_fn!(
+                vk_instance,
+                vkGetPhysicalDeviceMemoryProperties: PFN_vkGetPhysicalDeviceMemoryProperties
+            );
+            let enumerate_extensions = load_instance_fn!(
+                vk_instance,
+                vkEnumerateDeviceExtensionProperties: PFN_vkEnumerateDeviceExtensionProperties
+            );
+
+            let mut count = 0;
+            check_vk(
+                enumerate_physical_devices(vk_instance, &mut count, ptr::null_mut()),
+                "vkEnumeratePhysicalDevices",
+            )?;
+            let mut vk_physical_devices = vec![ptr::null_mut(); count as usize];
+            check_vk(
+                enumerate_physical_devices(
+                    vk_instance,
+                    &mut count,
+                    vk_physical_devices.as_mut_ptr(),
+                ),
+                "vkEnumeratePhysicalDevices",
+            )?;
+            vk_physical_devices.truncate(count as usize);
+
+            let mut physical_devices = Vec::with_capacity(vk_physical_devices.len());
+            for vk_physical_device in vk_physical_devices {
+                let mut c_properties = mem::zeroed();
+                get_properties(vk_physical_device, &mut c_properties);
+                let properties = PhysicalDeviceProperties::from_c(c_properties);
+
+                let mut extension_count = 0;
+                check_vk(
+                    enumerate_extensions(
+                        vk_physical_device,
+                        ptr::null(),
+                        &mut extension_count,
+                        ptr::null_mut(),
+                    ),
+                    "vkEnumerateDeviceExtensionProperties",
+                )?;
+                let mut extensions = vec![
+                    mem::zeroed::<daxa_sys::VkExtensionProperties>();
+                    extension_count as usize
+                ];
+                check_vk(
+                    enumerate_extensions(
+                        vk_physical_device,
+                        ptr::null(),
+                        &mut extension_count,
+                        extensions.as_mut_ptr(),
+                    ),
+                    "vkEnumerateDeviceExtensionProperties",
+                )?;
+                extensions.truncate(extension_count as usize);
+                let has_extension = |name: &[u8]| {
+                    extensions.iter().any(|extension| {
+                        ffi::CStr::from_ptr(extension.extensionName.as_ptr()).to_bytes() == name
+                    })
+                };
+
+                let mut features = DeviceFlags::empty();
+                let mut unsupported_reasons = vec![];
+
+                if properties.api_version() < vk_version(1, 3) {
+                    unsupported_reasons.push("vulkan 1.3 is not supported");
+                } else {
+                    let has_mesh_shader = has_extension(b"VK_EXT_mesh_shader");
+                    let has_image_atomic64 = has_extension(b"VK_EXT_shader_image_atomic_int64");
+
+                    let mut mesh_shader =
+                        mem::zeroed::<daxa_sys::VkPhysicalDeviceMeshShaderFeaturesEXT>();
+                    mesh_shader.sType = daxa_sys::VkStructureType_VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_MESH_SHADER_FEATURES_EXT;
+                    let mut image_atomic64 = mem::zeroed::<
+                        daxa_sys::VkPhysicalDeviceShaderImageAtomicInt64FeaturesEXT,
+                    >();
+                    image_atomic64.sType = daxa_sys::VkStructureType_VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SHADER_IMAGE_ATOMIC_INT64_FEATURES_EXT;
+                    let mut vk13 = mem::zeroed::<daxa_sys::VkPhysicalDeviceVulkan13Features>();
+                    vk13.sType =
+                        daxa_sys::VkStructureType_VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_VULKAN_1_3_FEATURES;
+                    let mut vk12 = mem::zeroed::<daxa_sys::VkPhysicalDeviceVulkan12Features>();
+                    vk12.sType =
+                        daxa_sys::VkStructureType_VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_VULKAN_1_2_FEATURES;
+                    let mut features2 = mem::zeroed::<daxa_sys::VkPhysicalDeviceFeatures2>();
+                    features2.sType =
+                        daxa_sys::VkStructureType_VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_FEATURES_2;
+
+                    //extension structs may only be chained when the extension is present
+                    let mut next: *mut ffi::c_void = ptr::null_mut();
+                    if has_mesh_shader {
+                        mesh_shader.pNext = next;
+                        next = (&mut mesh_shader
+                            as *mut daxa_sys::VkPhysicalDeviceMeshShaderFeaturesEXT)
+                            .cast();
+                    }
+                    if has_image_atomic64 {
+                        image_atomic64.pNext = next;
+                        next = (&mut image_atomic64
+                            as *mut daxa_sys::VkPhysicalDeviceShaderImageAtomicInt64FeaturesEXT)
+                            .cast();
+                    }
+                    vk13.pNext = next;
+                    vk12.pNext =
+                        (&mut vk13 as *mut daxa_sys::VkPhysicalDeviceVulkan13Features).cast();
+                    features2.pNext =
+                        (&mut vk12 as *mut daxa_sys::VkPhysicalDeviceVulkan12Features).cast();
+                    get_features(vk_physical_device, &mut features2);
+
+                    let bindless = vk12.descriptorIndexing != 0
+                        && vk12.runtimeDescriptorArray != 0
+                        && vk12.descriptorBindingPartiallyBound != 0
+                        && vk12.descriptorBindingUpdateUnusedWhilePending != 0
+                        && vk12.descriptorBindingSampledImageUpdateAfterBind != 0
+                        && vk12.descriptorBindingStorageImageUpdateAfterBind != 0
+                        && vk12.descriptorBindingStorageBufferUpdateAfterBind != 0
+                        && vk12.shaderSampledImageArrayNonUniformIndexing != 0
+                        && vk12.shaderStorageImageArrayNonUniformIndexing != 0
+                        && vk12.shaderStorageBufferArrayNonUniformIndexing != 0;
+                    if !bindless {
+                        unsupported_reasons.push("bindless descriptor indexing is not supported");
+                    }
+                    if vk12.bufferDeviceAddress == 0 {
+                        unsupported_reasons.push("buffer device address is not supported");
+                    }
+                    if vk13.dynamicRendering == 0 || vk13.synchronization2 == 0 {
+                        unsupported_reasons
+                            .push("dynamic rendering or synchronization2 is not supported");
+                    }
+
+                    features.set(
+                        DeviceFlags::BUFFER_DEVICE_ADDRESS_CAPTURE_REPLAY_BIT,
+                        vk12.bufferDeviceAddressCaptureReplay != 0,
+                    );
+                    features.set(
+                        DeviceFlags::CONSERVATIVE_RASTERIZATION,
+                        has_extension(b"VK_EXT_conservative_rasterization"),
+                    );
+                    features.set(
+                        DeviceFlags::MESH_SHADER_BIT,
+                        has_mesh_shader
+                            && mesh_shader.meshShader != 0
+                            && mesh_shader.taskShader != 0,
+                    );
+                    features.set(
+                        DeviceFlags::SHADER_ATOMIC64,
+                        vk12.shaderBufferInt64Atomics != 0 && vk12.shaderSharedInt64Atomics != 0,
+                    );
+                    features.set(
+                        DeviceFlags::IMAGE_ATOMIC64,
+                        has_image_atomic64 && image_atomic64.shaderImageInt64Atomics != 0,
+                    );
+                    features.set(
+                        DeviceFlags::VK_MEMORY_MODEL,
+                        vk12.vulkanMemoryModel != 0 && vk12.vulkanMemoryModelDeviceScope != 0,
+                    );
+                }
+
+                let mut memory_properties =
+                    mem::zeroed::<daxa_sys::VkPhysicalDeviceMemoryProperties>();
+                get_memory_properties(vk_physical_device, &mut memory_properties);
+                let memory_heaps = memory_properties.memoryHeaps
+                    [..memory_properties.memoryHeapCount as usize]
+                    .iter()
+                    .map(|heap| MemoryHeap {
+                        size: heap.size,
+                        device_local: heap.flags
+                            & daxa_sys::VkMemoryHeapFlagBits_VK_MEMORY_HEAP_DEVICE_LOCAL_BIT as u32
+                            != 0,
+                    })
+                    .collect();
+
+                physical_devices.push(PhysicalDevice {
+                    properties,
+                    features,
+                    memory_heaps,
+                    unsupported_reasons,
+                });
+            }
+
+            Ok(physical_devices)
+        }
+    }
+
     pub fn info(&self) -> &InstanceInfo {
         unsafe {
-            daxa_sys::daxa_instance_info(self.instance)
+            daxa_sys::daxa_instance_info(self.inner.handle)
                 .cast::<InstanceInfo>()
                 .as_ref()
                 .unwrap()
         }
     }
 }
-
-// impl Drop for Instance {
-//     fn drop(&mut self) {
-//         unsafe {
-//             daxa_sys::daxa_dvc_destroy_instance(self.instance);
-//         }
-//     }
-// }
@@ -1,17 +1,1329 @@
+mod command_recorder;
 mod device;
+mod frame_timeline;
+mod future;
 mod instance;
+mod loader;
 mod pipeline;
+#[cfg(feature = "shader-compiler")]
+mod pipeline_manager;
+mod reflection;
+#[cfg(feature = "shader-compiler")]
+mod shader_compiler;
+#[cfg(feature = "shader-compiler")]
+mod shader_variants;
+mod swapchain;
+mod sync;
 mod types;
+pub use command_recorder::*;
+pub use device::*;
+pub use frame_timeline::*;
+pub use future::*;
 pub use instance::*;
+pub use pipeline::*;
+#[cfg(feature = "shader-compiler")]
+pub use pipeline_manager::*;
+pub use reflection::*;
+#[cfg(feature = "shader-compiler")]
+pub use shader_compiler::*;
+#[cfg(feature = "shader-compiler")]
+pub use shader_variants::*;
+pub use swapchain::*;
+pub use sync::*;
 pub use types::*;
 
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn it_works() {
-        let _instance = crate::Instance::new(&crate::InstanceInfo {
+    fn test_instance() -> crate::Instance {
+        crate::Instance::new(&crate::InstanceInfo {
             flags: crate::InstanceFlags::DEBUG_UTIL,
         })
+        .unwrap()
+    }
+
+    fn test_device() -> (crate::Instance, crate::Device) {
+        let instance = test_instance();
+        let device = instance
+            .create_device(&crate::DeviceInfo::default())
+            .unwrap();
+        (instance, device)
+    }
+
+    //unique per test and process, removed again when the test ends, even by a failed assert
+    #[cfg(feature = "shader-compiler")]
+    struct TestDirectory(std::path::PathBuf);
+
+    #[cfg(feature = "shader-compiler")]
+    impl TestDirectory {
+        fn new(test: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("daxa_rs_{}_{}", test, std::process::id()));
+            std::fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+
+        fn join(&self, file: &str) -> std::path::PathBuf {
+            self.0.join(file)
+        }
+    }
+
+    #[cfg(feature = "shader-compiler")]
+    impl Drop for TestDirectory {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        let _instance = test_instance();
+    }
+
+    #[test]
+    fn create_device() {
+        let instance = test_instance();
+
+        let device = instance
+            .create_device(
+                &crate::DeviceInfo::builder()
+                    .max_allowed_images(64)
+                    .name("test device")
+                    .build(),
+            )
+            .unwrap();
+
+        assert_eq!(device.info().name(), "test device");
+        assert_eq!(device.info().max_allowed_images(), 64);
+    }
+
+    #[test]
+    fn create_device_with_capturing_selector() {
+        let instance = test_instance();
+
+        let last_vendor = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let seen_vendor = last_vendor.clone();
+
+        instance
+            .create_device(
+                &crate::DeviceInfo::builder()
+                    .selector(move |properties| {
+                        *seen_vendor.lock().unwrap() = Some(properties.vendor_id());
+                        assert!(!properties.name().is_empty());
+                        crate::default_device_selector(properties)
+                    })
+                    .build(),
+            )
+            .unwrap();
+
+        assert!(last_vendor.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn enumerate_physical_devices() {
+        let instance = test_instance();
+
+        let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+        assert!(!physical_devices.is_empty());
+        for physical_device in &physical_devices {
+            assert!(!physical_device.memory_heaps().is_empty());
+            let report = physical_device.to_string();
+            assert!(report.starts_with(physical_device.properties().name()));
+            assert!(report.contains("heap 0: "));
+        }
+    }
+
+    #[test]
+    fn result_codes_convert_checked() {
+        use crate::Result;
+
+        assert_eq!(Result::try_from(0), Ok(Result::Success));
+        assert_eq!(
+            Result::try_from(Result::ErrorDeviceLost as i32),
+            Ok(Result::ErrorDeviceLost)
+        );
+        assert!(Result::ErrorOutOfDateKhr.is_error());
+        assert!(!Result::SuboptimalKhr.is_error());
+        assert_eq!(Result::try_from(0x7fff_fffe), Err(0x7fff_fffe));
+
+        let error = crate::DaxaError::new(
+            Result::ErrorOutOfDeviceMemory as i32,
+            "create_buffer",
+            "vertices",
+        );
+        assert_eq!(error.result(), Some(Result::ErrorOutOfDeviceMemory));
+        assert!(error.to_string().contains("vertices"));
+    }
+
+    #[test]
+    fn info_lowering_owns_its_name() {
+        use crate::types::AsCInfo;
+
+        let mut info = crate::BufferInfo {
+            size: 256,
+            name: String::from("staging"),
+            ..Default::default()
+        };
+        let c_info = info.as_c_info().unwrap();
+
+        let c_info = unsafe { &*c_info.as_ptr() };
+        let name = unsafe {
+            std::slice::from_raw_parts(c_info.name.data.cast::<u8>(), c_info.name.size as usize)
+        };
+        assert_eq!(c_info.size, 256);
+        assert_eq!(name, b"staging");
+
+        //daxa would cut the name off at the nul, so it is refused instead
+        info.name = String::from("staging\0ignored");
+        assert!(info.as_c_info().is_err());
+    }
+
+    #[test]
+    fn dropped_buffers_are_destroyed_on_collect_garbage() {
+        let (_instance, device) = test_device();
+
+        let buffer = device
+            .create_buffer(&crate::BufferInfo {
+                size: 64,
+                name: String::from("zombie"),
+                ..Default::default()
+            })
+            .unwrap();
+        let id = buffer.id();
+        let shared = buffer.clone();
+
+        drop(buffer);
+        device.collect_garbage().unwrap();
+        assert!(device.is_buffer_valid(id));
+
+        drop(shared);
+        device.collect_garbage().unwrap();
+        assert!(!device.is_buffer_valid(id));
+    }
+
+    #[test]
+    fn mapped_buffers_check_host_access() {
+        let (_instance, device) = test_device();
+
+        let device_local = device
+            .create_buffer(&crate::BufferInfo {
+                size: 64,
+                name: String::from("device local"),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(matches!(
+            device_local.map::<u32>(),
+            Err(crate::MapError::NotHostVisible)
+        ));
+
+        let host = device
+            .create_buffer(&crate::BufferInfo {
+                size: 64,
+                allocate_info: crate::MemoryFlags::HOST_ACCESS_RANDOM,
+                name: String::from("host"),
+            })
+            .unwrap();
+        assert!(matches!(
+            host.map::<[u8; 48]>(),
+            Err(crate::MapError::SizeMismatch { .. })
+        ));
+        {
+            let mut mapping = host.map::<u32>().unwrap();
+            assert_eq!(mapping.len(), 16);
+            assert!(matches!(
+                host.map::<u32>(),
+                Err(crate::MapError::AlreadyMapped)
+            ));
+            mapping[3] = 7;
+        }
+        assert_eq!(host.map::<u32>().unwrap()[3], 7);
+
+        let upload = device
+            .create_buffer(&crate::BufferInfo {
+                size: 64,
+                allocate_info: crate::MemoryFlags::SEQUENTIAL_WRITE,
+                name: String::from("upload"),
+            })
+            .unwrap();
+        assert!(matches!(
+            upload.map::<u32>(),
+            Err(crate::MapError::WriteOnly)
+        ));
+        upload.map_write::<u32>().unwrap().write_slice(0, &[1; 16]);
+    }
+
+    #[test]
+    fn record_transfer_commands() {
+        let (_instance, device) = test_device();
+
+        let src = device
+            .create_buffer(&crate::BufferInfo {
+                size: 256,
+                name: String::from("src"),
+                ..Default::default()
+            })
+            .unwrap();
+        let dst = device
+            .create_buffer(&crate::BufferInfo {
+                size: 256,
+                name: String::from("dst"),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let mut recorder = device
+            .create_command_recorder(&crate::CommandRecorderInfo {
+                name: String::from("transfer"),
+            })
+            .unwrap();
+        recorder
+            .clear_buffer(&crate::BufferClearInfo {
+                buffer: src.id(),
+                offset: 0,
+                size: 256,
+                clear_value: 0xdeadbeef,
+            })
+            .unwrap();
+        recorder
+            .copy_buffer_to_buffer(&crate::BufferCopyInfo {
+                src_buffer: src.id(),
+                src_offset: 0,
+                dst_buffer: dst.id(),
+                dst_offset: 0,
+                size: 256,
+            })
+            .unwrap();
+        recorder.complete().unwrap();
+    }
+
+    #[test]
+    fn render_pass_scope() {
+        let (_instance, device) = test_device();
+
+        let image = device
+            .create_image(&crate::ImageInfo {
+                extent: crate::Extent::TwoDim(64, 64),
+                usage: crate::ImageUsageFlags::COLOR_ATTACHMENT,
+                name: String::from("target"),
+                ..Default::default()
+            })
+            .unwrap();
+        let view = device
+            .create_image_view(&crate::ImageViewInfo {
+                ty: crate::ImageViewType::TWO_DIM,
+                format: crate::Format::R8G8B8A8_UNORM,
+                image: image.id(),
+                slice: Default::default(),
+                name: String::from("target view"),
+            })
+            .unwrap();
+        //no fragment shader, the attachment keeps its clear color
+        let info = crate::RasterPipelineInfo {
+            vertex_shader_info: Some(crate::ShaderInfo::from_words(&POINT_VERTEX).unwrap()),
+            raster: crate::RasterizerInfo {
+                primitive_topology: crate::PrimitiveTopology::PointList,
+                ..Default::default()
+            },
+            name: String::from("points"),
+            ..Default::default()
+        }
+        .with_color_attachment(0, crate::Format::R8G8B8A8_UNORM, None)
+        .unwrap();
+        let pipeline = device.create_raster_pipeline::<()>(&info).unwrap();
+        let indices = device
+            .create_buffer(&crate::BufferInfo {
+                size: 64,
+                name: String::from("indices"),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let mut recorder = device
+            .create_command_recorder(&crate::CommandRecorderInfo {
+                name: String::from("render"),
+            })
+            .unwrap();
+        let attachment = crate::RenderAttachmentInfo {
+            image_view: view.id(),
+            layout: crate::ImageLayout::AttachmentOptimal,
+            load_op: crate::AttachmentLoadOp::Clear,
+            store_op: crate::AttachmentStoreOp::Store,
+            clear_value: crate::ClearValue::Float([0.0, 0.0, 0.0, 1.0]),
+        };
+        let render_area = crate::Rect2D {
+            width: 64,
+            height: 64,
+            ..Default::default()
+        };
+        let mut render = recorder
+            .begin_renderpass(&crate::RenderPassBeginInfo {
+                color_attachments: &[attachment],
+                render_area,
+                ..Default::default()
+            })
+            .unwrap();
+        render.set_pipeline(&pipeline);
+        //records nothing, there are no empty push constant ranges
+        render.push_constant(&()).unwrap();
+        render.draw(&crate::DrawInfo {
+            vertex_count: 1,
+            ..Default::default()
+        });
+        render.set_index_buffer(&crate::SetIndexBufferInfo {
+            buffer: indices.id(),
+            offset: 0,
+            index_type: crate::IndexType::Uint32,
+        });
+        render.draw_indexed(&crate::DrawIndexedInfo {
+            index_count: 16,
+            ..Default::default()
+        });
+        assert!(render.draw_mesh_tasks(1, 1, 1).is_err());
+        render.end();
+        assert!(matches!(
+            recorder.begin_renderpass(&crate::RenderPassBeginInfo {
+                color_attachments: &[attachment; crate::MAX_COLOR_ATTACHMENTS + 1],
+                render_area,
+                ..Default::default()
+            }),
+            Err(crate::RenderPassError::TooManyColorAttachments(9))
+        ));
+        recorder.complete().unwrap();
+    }
+
+    #[test]
+    fn push_constant_size_is_checked_against_limits() {
+        let (_instance, device) = test_device();
+
+        assert_eq!(
+            crate::pipeline::push_constant_size::<()>(&device).unwrap(),
+            0
+        );
+        assert_eq!(
+            crate::pipeline::push_constant_size::<[u32; 4]>(&device).unwrap(),
+            16
+        );
+        assert!(matches!(
+            crate::pipeline::push_constant_size::<[u8; 1 << 16]>(&device),
+            Err(crate::PipelineCreateError::PushConstantTooLarge { .. })
+        ));
+    }
+
+    //a vertex shader writing a constant position
+    #[rustfmt::skip]
+    const POINT_VERTEX: [u32; 67] = [
+        0x0723_0203, 0x0001_0000, 0, 12, 0,
+        0x0002_0011, 1,
+        0x0003_000e, 0, 1,
+        0x0006_000f, 0, 1, 0x6e69_616d, 0, 2,
+        0x0004_0047, 2, 11, 0,
+        0x0002_0013, 3,
+        0x0003_0021, 4, 3,
+        0x0003_0016, 5, 32,
+        0x0004_0017, 6, 5, 4,
+        0x0004_0020, 7, 3, 6,
+        0x0004_003b, 7, 2, 3,
+        0x0004_002b, 5, 8, 0,
+        0x0004_002b, 5, 9, 0x3f80_0000,
+        0x0007_002c, 6, 10, 8, 8, 8, 9,
+        0x0005_0036, 3, 1, 0, 4,
+        0x0002_00f8, 11,
+        0x0003_003e, 2, 10,
+        0x0001_00fd,
+        0x0001_0038,
+    ];
+
+    //a fragment shader writing push constants { vec4 tint; } to location 0
+    #[rustfmt::skip]
+    const TINT_FRAGMENT: [u32; 95] = [
+        0x0723_0203, 0x0001_0000, 0, 17, 0,
+        0x0002_0011, 1,
+        0x0003_000e, 0, 1,
+        0x0006_000f, 4, 1, 0x6e69_616d, 0, 2,
+        0x0003_0010, 1, 7,
+        0x0004_0047, 2, 30, 0,
+        0x0003_0047, 8, 2,
+        0x0005_0048, 8, 0, 35, 0,
+        0x0002_0013, 3,
+        0x0003_0021, 4, 3,
+        0x0003_0016, 5, 32,
+        0x0004_0017, 6, 5, 4,
+        0x0004_0020, 7, 3, 6,
+        0x0004_003b, 7, 2, 3,
+        0x0003_001e, 8, 6,
+        0x0004_0020, 9, 9, 8,
+        0x0004_003b, 9, 10, 9,
+        0x0004_0015, 11, 32, 1,
+        0x0004_002b, 11, 12, 0,
+        0x0004_0020, 13, 9, 6,
+        0x0005_0036, 3, 1, 0, 4,
+        0x0002_00f8, 14,
+        0x0005_0041, 13, 15, 10, 12,
+        0x0004_003d, 6, 16, 15,
+        0x0003_003e, 2, 16,
+        0x0001_00fd,
+        0x0001_0038,
+    ];
+
+    #[test]
+    fn raster_pipeline_push_constants_follow_the_bound_pipeline() {
+        let (_instance, device) = test_device();
+
+        let info = crate::RasterPipelineInfo {
+            vertex_shader_info: Some(crate::ShaderInfo::from_words(&POINT_VERTEX).unwrap()),
+            fragment_shader_info: Some(crate::ShaderInfo::from_words(&TINT_FRAGMENT).unwrap()),
+            raster: crate::RasterizerInfo {
+                primitive_topology: crate::PrimitiveTopology::PointList,
+                ..Default::default()
+            },
+            name: String::from("tint"),
+            ..Default::default()
+        }
+        .with_color_attachment(0, crate::Format::R8G8B8A8_UNORM, None)
+        .unwrap();
+        let pipeline = device.create_raster_pipeline::<[f32; 4]>(&info).unwrap();
+
+        let image = device
+            .create_image(&crate::ImageInfo {
+                extent: crate::Extent::TwoDim(64, 64),
+                usage: crate::ImageUsageFlags::COLOR_ATTACHMENT,
+                name: String::from("target"),
+                ..Default::default()
+            })
+            .unwrap();
+        let view = device
+            .create_image_view(&crate::ImageViewInfo {
+                ty: crate::ImageViewType::TWO_DIM,
+                format: crate::Format::R8G8B8A8_UNORM,
+                image: image.id(),
+                slice: Default::default(),
+                name: String::from("target view"),
+            })
+            .unwrap();
+
+        let mut recorder = device
+            .create_command_recorder(&crate::CommandRecorderInfo {
+                name: String::from("tint"),
+            })
+            .unwrap();
+        let mut render = recorder
+            .begin_renderpass(&crate::RenderPassBeginInfo {
+                color_attachments: &[crate::RenderAttachmentInfo {
+                    image_view: view.id(),
+                    layout: crate::ImageLayout::AttachmentOptimal,
+                    load_op: crate::AttachmentLoadOp::Clear,
+                    store_op: crate::AttachmentStoreOp::Store,
+                    clear_value: crate::ClearValue::Float([0.0, 0.0, 0.0, 1.0]),
+                }],
+                render_area: crate::Rect2D {
+                    width: 64,
+                    height: 64,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(matches!(
+            render.push_constant(&[1.0f32; 4]),
+            Err(crate::PushConstantError::NoPipelineBound { .. })
+        ));
+        render.set_pipeline(&pipeline);
+        render.push_constant(&[1.0f32, 0.0, 0.0, 1.0]).unwrap();
+        assert!(matches!(
+            render.push_constant(&0u32),
+            Err(crate::PushConstantError::TypeMismatch { .. })
+        ));
+        render.draw(&crate::DrawInfo {
+            vertex_count: 1,
+            ..Default::default()
+        });
+        render.end();
+        recorder.complete().unwrap();
+    }
+
+    #[test]
+    fn raster_pipeline_fragment_outputs_need_color_attachments() {
+        let mut second_output = TINT_FRAGMENT;
+        //the literal of the output's Location decoration
+        second_output[22] = 1;
+        let reflection = crate::ShaderInfo::from_words(&second_output)
+            .unwrap()
+            .reflect()
+            .unwrap();
+        let locations = reflection
+            .entry_point("main")
+            .unwrap()
+            .fragment_outputs
+            .iter()
+            .map(|output| output.location)
+            .collect::<Vec<_>>();
+        assert_eq!(locations, [1]);
+
+        let info = crate::RasterPipelineInfo {
+            vertex_shader_info: Some(crate::ShaderInfo::from_words(&POINT_VERTEX).unwrap()),
+            ..Default::default()
+        }
+        .with_color_attachment(0, crate::Format::R8G8B8A8_UNORM, None)
+        .unwrap();
+        let first = crate::RasterPipelineInfo {
+            fragment_shader_info: Some(crate::ShaderInfo::from_words(&TINT_FRAGMENT).unwrap()),
+            ..info.clone()
+        };
+        first.check_shaders::<[f32; 4]>().unwrap();
+        let second = crate::RasterPipelineInfo {
+            fragment_shader_info: Some(crate::ShaderInfo::from_words(&second_output).unwrap()),
+            ..info
+        };
+        assert!(matches!(
+            second.check_shaders::<[f32; 4]>(),
+            Err(crate::PipelineCreateError::UnboundFragmentOutput { location: 1 })
+        ));
+        assert!(matches!(
+            crate::RasterPipelineInfo::default().with_color_attachment(
+                crate::MAX_COLOR_ATTACHMENTS,
+                crate::Format::R8G8B8A8_UNORM,
+                None
+            ),
+            Err(crate::PipelineCreateError::ColorAttachmentOutOfRange { location: 8 })
+        ));
+    }
+
+    #[test]
+    fn image_transitions_and_split_barriers() {
+        let (_instance, device) = test_device();
+
+        let image = device
+            .create_image(&crate::ImageInfo {
+                extent: crate::Extent::TwoDim(16, 16),
+                usage: crate::ImageUsageFlags::TRANSFER_DST | crate::ImageUsageFlags::SAMPLED,
+                name: String::from("transitioned"),
+                ..Default::default()
+            })
+            .unwrap();
+        let event = device
+            .create_event(&crate::EventInfo {
+                name: String::from("split"),
+            })
+            .unwrap();
+
+        let transfer_write = crate::Access::new(
+            crate::PipelineStageFlags::TRANSFER_BIT,
+            crate::AccessTypeFlags::WRITE,
+        );
+        let mut recorder = device
+            .create_command_recorder(&crate::CommandRecorderInfo {
+                name: String::from("barriers"),
+            })
+            .unwrap();
+        recorder
+            .pipeline_barriers(
+                &[],
+                &[crate::ImageMemoryBarrierInfo {
+                    src_access: crate::Access::default(),
+                    dst_access: transfer_write,
+                    src_layout: crate::ImageLayout::Undefined,
+                    dst_layout: crate::ImageLayout::TransferDstOptimal,
+                    image_slice: Default::default(),
+                    image: image.id(),
+                }],
+            )
+            .unwrap();
+        recorder
+            .clear_image(&crate::ImageClearInfo {
+                image: image.id(),
+                image_layout: crate::ImageLayout::TransferDstOptimal,
+                clear_value: crate::ClearValue::Float([1.0; 4]),
+                dst_slice: Default::default(),
+            })
+            .unwrap();
+
+        let to_sampled = [crate::ImageMemoryBarrierInfo {
+            src_access: transfer_write,
+            dst_access: crate::Access::new(
+                crate::PipelineStageFlags::FRAGMENT_SHADER_BIT,
+                crate::AccessTypeFlags::READ,
+            ),
+            src_layout: crate::ImageLayout::TransferDstOptimal,
+            dst_layout: crate::ImageLayout::ReadOnlyOptimal,
+            image_slice: Default::default(),
+            image: image.id(),
+        }];
+        let split = crate::EventSignalInfo {
+            memory_barriers: &[],
+            image_barriers: &to_sampled,
+            event: &event,
+        };
+        recorder.signal_event(&split).unwrap();
+        recorder.wait_events(&[split]).unwrap();
+        recorder
+            .reset_event(&event, crate::PipelineStageFlags::FRAGMENT_SHADER_BIT)
+            .unwrap();
+        recorder.complete().unwrap();
+    }
+
+    #[test]
+    fn submit_with_timeline_signal() {
+        let (_instance, device) = test_device();
+
+        let timeline = device
+            .create_timeline_semaphore(&crate::TimelineSemaphoreInfo {
+                initial_value: 0,
+                name: String::from("timeline"),
+            })
+            .unwrap();
+        let recorder = device
+            .create_command_recorder(&crate::CommandRecorderInfo {
+                name: String::from("empty"),
+            })
+            .unwrap();
+        let commands = recorder.complete().unwrap();
+
+        device
+            .submit(&crate::CommandSubmitInfo {
+                command_lists: &[commands],
+                signal_timeline_semaphores: &[(timeline.clone(), 1)],
+                ..Default::default()
+            })
+            .unwrap();
+        device.wait_idle().unwrap();
+    }
+
+    #[test]
+    fn swapchain_rejects_unsupported_windows() {
+        use raw_window_handle::{
+            HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
+            WebDisplayHandle, WebWindowHandle,
+        };
+
+        struct WebCanvas;
+
+        unsafe impl HasRawWindowHandle for WebCanvas {
+            fn raw_window_handle(&self) -> RawWindowHandle {
+                RawWindowHandle::Web(WebWindowHandle::empty())
+            }
+        }
+
+        unsafe impl HasRawDisplayHandle for WebCanvas {
+            fn raw_display_handle(&self) -> RawDisplayHandle {
+                RawDisplayHandle::Web(WebDisplayHandle::empty())
+            }
+        }
+
+        let (_instance, device) = test_device();
+
+        let info = crate::SwapchainInfo::builder()
+            .surface_format_selector(|format| {
+                (format == crate::Format::B8G8R8A8_SRGB).then_some(100)
+            })
+            .present_mode(crate::PresentMode::Mailbox)
+            .name("canvas")
+            .build();
+        let result = unsafe { device.create_swapchain(&WebCanvas, &info) };
+        assert!(matches!(
+            result,
+            Err(crate::SwapchainCreateError::UnsupportedWindow)
+        ));
+    }
+
+    #[test]
+    fn headless_swapchain_reads_back_presented_frames() {
+        let (_instance, device) = test_device();
+
+        let frames = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let captured = frames.clone();
+        let swapchain = device
+            .create_headless_swapchain(
+                crate::Extent2D {
+                    width: 4,
+                    height: 4,
+                },
+                &crate::SwapchainInfo::builder()
+                    .surface_format_selector(|format| {
+                        (format == crate::Format::R8G8B8A8_UNORM).then_some(1)
+                    })
+                    .name("headless")
+                    .build(),
+                Some(Box::new(move |frame: &crate::HeadlessFrame| {
+                    captured
+                        .lock()
+                        .unwrap()
+                        .push((frame.index, frame.data.to_vec()));
+                })),
+            )
+            .unwrap();
+        assert!(swapchain.is_headless());
+        assert_eq!(swapchain.format(), crate::Format::R8G8B8A8_UNORM);
+        assert!(matches!(
+            device.present(&crate::PresentInfo {
+                wait_binary_semaphores: &[],
+                swapchain: &swapchain,
+            }),
+            Err(crate::PresentError::NotAcquired)
+        ));
+
+        for _ in 0..3 {
+            let image = swapchain.acquire_next_image().unwrap();
+            let mut recorder = device
+                .create_command_recorder(&crate::CommandRecorderInfo {
+                    name: String::from("frame"),
+                })
+                .unwrap();
+            let transition = |src_layout, dst_layout| crate::ImageMemoryBarrierInfo {
+                src_access: crate::Access::new(
+                    crate::PipelineStageFlags::ALL_COMMANDS_BIT,
+                    crate::AccessTypeFlags::READ_WRITE,
+                ),
+                dst_access: crate::Access::new(
+                    crate::PipelineStageFlags::ALL_COMMANDS_BIT,
+                    crate::AccessTypeFlags::READ_WRITE,
+                ),
+                src_layout,
+                dst_layout,
+                image_slice: crate::ImageMipArraySlice::default(),
+                image,
+            };
+            recorder
+                .pipeline_barriers(
+                    &[],
+                    &[transition(
+                        crate::ImageLayout::Undefined,
+                        crate::ImageLayout::TransferDstOptimal,
+                    )],
+                )
+                .unwrap();
+            recorder
+                .clear_image(&crate::ImageClearInfo {
+                    image,
+                    image_layout: crate::ImageLayout::TransferDstOptimal,
+                    clear_value: crate::ClearValue::Float([1.0, 0.0, 0.0, 1.0]),
+                    dst_slice: crate::ImageMipArraySlice::default(),
+                })
+                .unwrap();
+            recorder
+                .pipeline_barriers(
+                    &[],
+                    &[transition(
+                        crate::ImageLayout::TransferDstOptimal,
+                        crate::ImageLayout::PresentSrc,
+                    )],
+                )
+                .unwrap();
+            let commands = recorder.complete().unwrap();
+
+            let present_semaphore = swapchain.current_present_semaphore();
+            device
+                .submit(&crate::CommandSubmitInfo {
+                    command_lists: &[commands],
+                    wait_binary_semaphores: &[swapchain.current_acquire_semaphore()],
+                    signal_binary_semaphores: &[present_semaphore.clone()],
+                    signal_timeline_semaphores: &[(
+                        swapchain.gpu_timeline_semaphore(),
+                        swapchain.current_cpu_timeline_value(),
+                    )],
+                    ..Default::default()
+                })
+                .unwrap();
+            device
+                .present(&crate::PresentInfo {
+                    wait_binary_semaphores: &[present_semaphore],
+                    swapchain: &swapchain,
+                })
+                .unwrap();
+        }
+        //with two frames in flight the first frame's slot was acquired again, later frames may be in flight
+        assert!(!frames.lock().unwrap().is_empty());
+        drop(swapchain);
+
+        let frames = frames.lock().unwrap();
+        assert_eq!(frames.len(), 3);
+        for (expected_index, (index, data)) in (1..).zip(frames.iter()) {
+            assert_eq!(*index, expected_index);
+            assert_eq!(data.len(), 4 * 4 * 4);
+            assert!(data.chunks(4).all(|texel| texel == [255, 0, 0, 255]));
+        }
+    }
+
+    #[test]
+    fn timeline_semaphore_host_signal_and_wait() {
+        let (_instance, device) = test_device();
+
+        let create = |name: &str| {
+            device
+                .create_timeline_semaphore(&crate::TimelineSemaphoreInfo {
+                    initial_value: 0,
+                    name: String::from(name),
+                })
+                .unwrap()
+        };
+        let streaming = create("streaming");
+        let upload = create("upload");
+        let no_wait = std::time::Duration::ZERO;
+
+        assert_eq!(streaming.value().unwrap(), 0);
+        assert!(matches!(
+            streaming.wait_for_value(1, no_wait),
+            Err(crate::WaitError::Timeout)
+        ));
+
+        streaming.signal(2).unwrap();
+        assert_eq!(streaming.value().unwrap(), 2);
+        streaming.wait_for_value(1, no_wait).unwrap();
+
+        let pending = [(upload.clone(), 1), (streaming.clone(), 2)];
+        assert_eq!(
+            crate::TimelineSemaphore::wait_any(&pending, no_wait).unwrap(),
+            1
+        );
+        assert!(matches!(
+            crate::TimelineSemaphore::wait_all(&pending, no_wait),
+            Err(crate::WaitError::Timeout)
+        ));
+        upload.signal(1).unwrap();
+        crate::TimelineSemaphore::wait_all(&pending, no_wait).unwrap();
+
+        assert!(matches!(
+            crate::TimelineSemaphore::wait_any(&[], no_wait),
+            Err(crate::WaitError::NoSemaphores)
+        ));
+        crate::TimelineSemaphore::wait_all(&[], no_wait).unwrap();
+        let (_other_instance, other_device) = test_device();
+        let other = other_device
+            .create_timeline_semaphore(&crate::TimelineSemaphoreInfo {
+                initial_value: 1,
+                name: String::from("other"),
+            })
+            .unwrap();
+        assert!(matches!(
+            crate::TimelineSemaphore::wait_all(&[(upload, 1), (other, 1)], no_wait),
+            Err(crate::WaitError::MixedDevices)
+        ));
+    }
+
+    #[test]
+    fn gpu_futures_resolve_on_timeline_values() {
+        struct ThreadWaker(std::thread::Thread);
+
+        impl std::task::Wake for ThreadWaker {
+            fn wake(self: std::sync::Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        fn block_on<F: std::future::Future>(future: F) -> F::Output {
+            let waker = std::sync::Arc::new(ThreadWaker(std::thread::current())).into();
+            let mut cx = std::task::Context::from_waker(&waker);
+            let mut future = std::pin::pin!(future);
+            loop {
+                match future.as_mut().poll(&mut cx) {
+                    std::task::Poll::Ready(output) => return output,
+                    std::task::Poll::Pending => std::thread::park(),
+                }
+            }
+        }
+
+        let (_instance, device) = test_device();
+        let timeline = device
+            .create_timeline_semaphore(&crate::TimelineSemaphoreInfo {
+                initial_value: 0,
+                name: String::from("jobs"),
+            })
+            .unwrap();
+
+        let empty = || {
+            device
+                .create_command_recorder(&crate::CommandRecorderInfo {
+                    name: String::from("empty"),
+                })
+                .unwrap()
+                .complete()
+                .unwrap()
+        };
+        let submitted = device
+            .submit_with_future(
+                &crate::CommandSubmitInfo {
+                    command_lists: &[empty()],
+                    ..Default::default()
+                },
+                (timeline.clone(), 1),
+            )
+            .unwrap();
+        block_on(submitted).unwrap();
+        assert_eq!(timeline.value().unwrap(), 1);
+
+        //signalled from another thread while the future is parked on the waiter
+        let host_signalled = crate::GpuFuture::new(timeline.clone(), 3);
+        let signaller = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            timeline.signal(3).unwrap();
+        });
+        block_on(host_signalled).unwrap();
+        signaller.join().unwrap();
+    }
+
+    #[test]
+    fn frame_timeline_paces_per_frame_slots() {
+        let (_instance, device) = test_device();
+
+        let mut timeline = crate::FrameTimeline::new(&device, 2, "frames").unwrap();
+        let mut used_by = timeline.per_frame(|_| Vec::new());
+        let semaphore = timeline.semaphore().clone();
+        for _ in 0..5 {
+            let frame = timeline.begin_frame().unwrap();
+            //the frame that used this slot before has finished on the gpu
+            if let Some(&previous) = used_by.get(&frame).last() {
+                assert_eq!(previous + 2, frame.number());
+                assert!(semaphore.value().unwrap() >= previous);
+            }
+            used_by.get_mut(&frame).push(frame.number());
+
+            let commands = device
+                .create_command_recorder(&crate::CommandRecorderInfo {
+                    name: String::from("frame"),
+                })
+                .unwrap()
+                .complete()
+                .unwrap();
+            device
+                .submit(&crate::CommandSubmitInfo {
+                    command_lists: &[commands],
+                    signal_timeline_semaphores: &[frame.signal()],
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        device.wait_idle().unwrap();
+
+        assert_eq!(used_by.into_inner(), vec![vec![2, 4], vec![1, 3, 5]]);
+    }
+
+    #[test]
+    fn compute_pipeline_from_validated_spirv() {
+        //an empty local_size 1 1 1 compute shader, one instruction per line
+        #[rustfmt::skip]
+        const EMPTY_COMPUTE: [u32; 29] = [
+            0x0723_0203, 0x0001_0000, 0, 5, 0,
+            0x0002_0011, 1,
+            0x0003_000e, 0, 1,
+            0x0005_000f, 5, 1, 0x6e69_616d, 0,
+            0x0006_0010, 1, 17, 1, 1, 1,
+            0x0002_0013, 2,
+            0x0003_0021, 3, 2,
+            0x0005_0036, 2, 1, 0, 3,
+            0x0002_00f8, 4,
+            0x0001_00fd,
+            0x0001_0038,
+        ];
+
+        let bytes: &[u8] = bytemuck::cast_slice(&EMPTY_COMPUTE);
+        assert!(crate::ShaderInfo::from_bytes(bytes).is_ok());
+        assert!(matches!(
+            crate::ShaderInfo::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(crate::SpirvError::InvalidLength(_))
+        ));
+        let mut unaligned = vec![0u8; bytes.len() + 1];
+        unaligned[1..].copy_from_slice(bytes);
+        assert!(matches!(
+            crate::ShaderInfo::from_bytes(&unaligned[1..]),
+            Err(crate::SpirvError::Misaligned)
+        ));
+        assert!(matches!(
+            crate::ShaderInfo::from_words(&EMPTY_COMPUTE[..4]),
+            Err(crate::SpirvError::TooShort(4))
+        ));
+        let swapped = EMPTY_COMPUTE.map(u32::swap_bytes);
+        assert!(matches!(
+            crate::ShaderInfo::from_words(&swapped),
+            Err(crate::SpirvError::WrongEndianness)
+        ));
+        let mut future_version = EMPTY_COMPUTE;
+        future_version[1] = 0x0002_0000;
+        assert!(matches!(
+            crate::ShaderInfo::from_words(&future_version),
+            Err(crate::SpirvError::UnsupportedVersion { major: 2, minor: 0 })
+        ));
+
+        let (_instance, device) = test_device();
+        let _pipeline = device
+            .create_compute_pipeline::<()>(&crate::ComputePipelineInfo {
+                shader_info: crate::ShaderInfo::from_words(&EMPTY_COMPUTE).unwrap(),
+                name: String::from("empty"),
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn shader_reflection_checks_pipeline_infos() {
+        //a local_size 8 4 1 compute shader declaring push constants { uint x; float y; }
+        #[rustfmt::skip]
+        const PUSH_CONSTANT_COMPUTE: [u32; 75] = [
+            0x0723_0203, 0x0001_0000, 0, 10, 0,
+            0x0002_0011, 1,
+            0x0003_000e, 0, 1,
+            0x0005_000f, 5, 1, 0x6e69_616d, 0,
+            0x0006_0010, 1, 17, 8, 4, 1,
+            0x0004_0006, 7, 0, 0x78,
+            0x0004_0006, 7, 1, 0x79,
+            0x0003_0047, 7, 2,
+            0x0005_0048, 7, 0, 35, 0,
+            0x0005_0048, 7, 1, 35, 4,
+            0x0002_0013, 2,
+            0x0003_0021, 3, 2,
+            0x0004_0015, 5, 32, 0,
+            0x0003_0016, 6, 32,
+            0x0004_001e, 7, 5, 6,
+            0x0004_0020, 8, 9, 7,
+            0x0004_003b, 8, 9, 9,
+            0x0005_0036, 2, 1, 0, 3,
+            0x0002_00f8, 4,
+            0x0001_00fd,
+            0x0001_0038,
+        ];
+
+        let shader_info = crate::ShaderInfo::from_words(&PUSH_CONSTANT_COMPUTE).unwrap();
+        let reflection = shader_info.reflect().unwrap();
+        assert_eq!(reflection.entry_points().len(), 1);
+        let entry_point = reflection.entry_point("main").unwrap();
+        assert_eq!(
+            entry_point.execution_model,
+            crate::ExecutionModel::GLCompute
+        );
+        assert_eq!(entry_point.local_size, Some([8, 4, 1]));
+        assert!(entry_point.fragment_outputs.is_empty());
+        let block = entry_point.push_constant.as_ref().unwrap();
+        assert_eq!(block.size, 8);
+        let members = block
+            .members
+            .iter()
+            .map(|member| (member.name.as_deref(), member.offset, member.size))
+            .collect::<Vec<_>>();
+        assert_eq!(members, [(Some("x"), 0, 4), (Some("y"), 4, 4)]);
+        let dispatch = entry_point.workgroup_count([100, 4, 1]).unwrap();
+        assert_eq!((dispatch.x, dispatch.y, dispatch.z), (13, 1, 1));
+
+        //cuts the OpLabel in half
+        let truncated = &PUSH_CONSTANT_COMPUTE[..PUSH_CONSTANT_COMPUTE.len() - 3];
+        assert!(matches!(
+            crate::ShaderInfo::from_words(truncated).unwrap().reflect(),
+            Err(crate::ReflectError::MalformedInstruction(71))
+        ));
+
+        let (_instance, device) = test_device();
+        let info = |shader_info| crate::ComputePipelineInfo {
+            shader_info,
+            name: String::from("push constant"),
+        };
+        let _pipeline = device
+            .create_compute_pipeline::<[u32; 2]>(&info(shader_info))
+            .unwrap();
+        assert!(matches!(
+            device.create_compute_pipeline::<u32>(&info(shader_info)),
+            Err(crate::PipelineCreateError::PushConstantMismatch {
+                shader_size: 8,
+                type_size: 4
+            })
+        ));
+        let other = std::ffi::CStr::from_bytes_with_nul(b"other\0").unwrap();
+        assert!(matches!(
+            device.create_compute_pipeline::<[u32; 2]>(&info(shader_info.with_entry_point(other))),
+            Err(crate::PipelineCreateError::MissingEntryPoint { .. })
+        ));
+    }
+
+    #[cfg(feature = "shader-compiler")]
+    #[test]
+    fn shader_compiler_maps_errors_to_included_files() {
+        let directory = TestDirectory::new("shader_compiler_maps_errors_to_included_files");
+        let main = directory.join("main.glsl");
+        std::fs::write(
+            &main,
+            "#version 460\n\
+             #include <daxa/daxa.glsl>\n\
+             #include \"common.glsl\"\n\
+             layout(local_size_x = LOCAL_SIZE) in;\n\
+             void main() {}\n",
+        )
         .unwrap();
+        let common = directory.join("common.glsl");
+        std::fs::write(&common, "#define UNUSED 0\n").unwrap();
+
+        let compiler = crate::ShaderCompiler::new().unwrap();
+        let options = crate::ShaderCompileOptions {
+            defines: vec![(String::from("LOCAL_SIZE"), String::from("64"))],
+            optimization: crate::OptimizationLevel::Performance,
+            ..Default::default()
+        };
+        let compiled = compiler
+            .compile_file(&main, crate::ExecutionModel::GLCompute, &options)
+            .unwrap();
+        assert!(compiled.includes().contains(&common));
+        let reflection = compiled.shader_info().reflect().unwrap();
+        assert_eq!(
+            reflection.entry_point("main").unwrap().local_size,
+            Some([64, 1, 1])
+        );
+
+        std::fs::write(&common, "#define UNUSED 0\nthis is not glsl\n").unwrap();
+        let Err(crate::ShaderCompileError::Compile(diagnostics)) =
+            compiler.compile_file(&main, crate::ExecutionModel::GLCompute, &options)
+        else {
+            panic!("compiling broken glsl succeeded");
+        };
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.file.as_deref(), Some(common.as_path()));
+        assert_eq!(diagnostic.line, Some(2));
+    }
+
+    #[cfg(feature = "shader-compiler")]
+    #[test]
+    fn pipeline_manager_hot_reloads_and_keeps_working_pipelines() {
+        let directory =
+            TestDirectory::new("pipeline_manager_hot_reloads_and_keeps_working_pipelines");
+        let main = directory.join("main.glsl");
+        let common = directory.join("common.glsl");
+        std::fs::write(
+            &main,
+            "#version 460\n\
+             #include \"common.glsl\"\n\
+             layout(local_size_x = LOCAL_SIZE) in;\n\
+             void main() {}\n",
+        )
+        .unwrap();
+        //explicit times, so the test does not depend on the file system's timestamp resolution
+        let edit = |path: &std::path::Path, content: &str, seconds: u64| {
+            std::fs::write(path, content).unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(
+                    std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(seconds),
+                )
+                .unwrap();
+        };
+        edit(&common, "#define LOCAL_SIZE 64\n", 1);
+
+        let (_instance, device) = test_device();
+        let mut manager =
+            crate::PipelineManager::new(&device, crate::ShaderCompileOptions::default()).unwrap();
+        let pipeline = manager
+            .add_compute_pipeline::<()>("reloaded", &main)
+            .unwrap();
+        assert!(matches!(
+            manager.add_compute_pipeline::<()>("reloaded", &main),
+            Err(crate::PipelineManagerError::DuplicateName(_))
+        ));
+        //kept alive so a rebuilt pipeline can not reuse its handle
+        let first = pipeline.get();
+
+        let report = manager.reload();
+        assert!(report.reloaded.is_empty() && report.errors.is_empty());
+
+        edit(&common, "#define LOCAL_SIZE\n", 2);
+        let report = manager.reload();
+        assert!(report.reloaded.is_empty());
+        assert!(matches!(
+            report.errors[..],
+            [crate::PipelineManagerError::Compile { .. }]
+        ));
+        assert_eq!(pipeline.get().handle(), first.handle());
+
+        edit(&common, "#define LOCAL_SIZE 32\n", 3);
+        let report = manager.reload();
+        assert_eq!(report.reloaded, ["reloaded"]);
+        assert_ne!(pipeline.get().handle(), first.handle());
+    }
+
+    #[cfg(feature = "shader-compiler")]
+    #[test]
+    fn shader_variant_set_compiles_lazily_and_evicts() {
+        let directory = TestDirectory::new("shader_variant_set_compiles_lazily_and_evicts");
+        let vertex = directory.join("vertex.glsl");
+        std::fs::write(
+            &vertex,
+            "#version 460\n\
+             void main() { gl_Position = vec4(0.0, 0.0, 0.0, 1.0); }\n",
+        )
+        .unwrap();
+        let fragment = directory.join("fragment.glsl");
+        std::fs::write(
+            &fragment,
+            "#version 460\n\
+             #include \"tint.glsl\"\n\
+             layout(location = 0) out vec4 color;\n\
+             void main() {\n\
+             #if ALPHA_TEST\n\
+             if (LOD_COUNT > 2) discard;\n\
+             #endif\n\
+             color = TINT;\n\
+             }\n",
+        )
+        .unwrap();
+        let tint = directory.join("tint.glsl");
+        std::fs::write(&tint, "#define TINT vec4(1.0)\n").unwrap();
+
+        let (_instance, device) = test_device();
+        let mut set = crate::ShaderVariantSet::<()>::new(
+            &device,
+            crate::ShaderVariantSetInfo {
+                name: String::from("material"),
+                sources: crate::RasterPipelineSources {
+                    stages: vec![
+                        (crate::RasterStage::Vertex, vertex),
+                        (crate::RasterStage::Fragment, fragment),
+                    ],
+                    template: crate::RasterPipelineInfo::default()
+                        .with_color_attachment(0, crate::Format::R8G8B8A8_UNORM, None)
+                        .unwrap(),
+                },
+                options: vec![
+                    crate::VariantOption::flag("ALPHA_TEST"),
+                    crate::VariantOption::range("LOD_COUNT", 1..=4),
+                ],
+                compile_options: crate::ShaderCompileOptions::default(),
+            },
+        )
+        .unwrap();
+        assert_eq!(set.permutation_count(), 8);
+        assert!(set.is_empty());
+
+        let opaque = set.get(&[]).unwrap();
+        let alpha_tested = set.get(&[("ALPHA_TEST", "1"), ("LOD_COUNT", "3")]).unwrap();
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            set.get(&[("ALPHA_TEST", "0")]).unwrap().handle(),
+            opaque.handle()
+        );
+        assert_eq!(set.len(), 2);
+        assert!(matches!(
+            set.get(&[("LOD_COUNT", "5")]),
+            Err(crate::ShaderVariantError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            set.get(&[("SKINNED", "1")]),
+            Err(crate::ShaderVariantError::UnknownOption(_))
+        ));
+
+        assert_eq!(set.evict_unused(), 0);
+        set.get(&[("ALPHA_TEST", "1"), ("LOD_COUNT", "3")]).unwrap();
+        assert_eq!(set.evict_unused(), 1);
+        assert_eq!(set.len(), 1);
+        assert_eq!(
+            set.get(&[("LOD_COUNT", "3"), ("ALPHA_TEST", "1")])
+                .unwrap()
+                .handle(),
+            alpha_tested.handle()
+        );
+
+        //the main sources are unchanged, only the include tells the variants apart
+        std::fs::write(&tint, "#define TINT vec4(0.5)\n").unwrap();
+        set.refresh_sources().unwrap();
+        assert!(set.is_empty());
+        assert_ne!(
+            set.get(&[("ALPHA_TEST", "1"), ("LOD_COUNT", "3")])
+                .unwrap()
+                .handle(),
+            alpha_tested.handle()
+        );
     }
 }
@@ -0,0 +1,78 @@
+use std::mem;
+use std::os;
+
+pub(crate) type GetInstanceProcAddr = unsafe extern "system" fn(
+    daxa_sys::VkInstance,
+    *const os::raw::c_char,
+) -> daxa_sys::PFN_vkVoidFunction;
+
+#[cfg(not(feature = "dynamic-loader"))]
+pub(crate) fn instance_proc_addr() -> Option<GetInstanceProcAddr> {
+    extern "system" {
+        fn vkGetInstanceProcAddr(
+            instance: daxa_sys::VkInstance,
+            name: *const os::raw::c_char,
+        ) -> daxa_sys::PFN_vkVoidFunction;
+    }
+
+    Some(vkGetInstanceProcAddr)
+}
+
+#[cfg(feature = "dynamic-loader")]
+pub(crate) fn instance_proc_addr() -> Option<GetInstanceProcAddr> {
+    use std::sync;
+
+    //opened once and never closed, daxa keeps calling into it for the rest of the process
+    static LOADER: sync::OnceLock<Option<(libloading::Library, GetInstanceProcAddr)>> =
+        sync::OnceLock::new();
+
+    LOADER
+        .get_or_init(|| {
+            let (library, get_instance_proc_addr) =
+                LOADER_NAMES.iter().find_map(|name| unsafe {
+                    let library = libloading::Library::new(name).ok()?;
+                    let get_instance_proc_addr = *library
+                        .get::<GetInstanceProcAddr>(b"vkGetInstanceProcAddr\0")
+                        .ok()?;
+                    Some((library, get_instance_proc_addr))
+                })?;
+            //daxa is built without the loader's exports, this is where its vulkan functions come from
+            unsafe { daxa_sys::daxa_sys_load_vulkan(get_instance_proc_addr) };
+            Some((library, get_instance_proc_addr))
+        })
+        .as_ref()
+        .map(|(_, get_instance_proc_addr)| *get_instance_proc_addr)
+}
+
+#[cfg(all(feature = "dynamic-loader", windows))]
+const LOADER_NAMES: &[&str] = &["vulkan-1.dll"];
+#[cfg(all(
+    feature = "dynamic-loader",
+    any(target_os = "macos", target_os = "ios")
+))]
+const LOADER_NAMES: &[&str] = &["libvulkan.dylib", "libvulkan.1.dylib", "libMoltenVK.dylib"];
+#[cfg(all(
+    feature = "dynamic-loader",
+    unix,
+    not(any(target_os = "macos", target_os = "ios"))
+))]
+const LOADER_NAMES: &[&str] = &["libvulkan.so.1", "libvulkan.so"];
+
+pub(crate) unsafe fn get_instance_proc_addr(
+    instance: daxa_sys::VkInstance,
+    name: *const os::raw::c_char,
+) -> daxa_sys::PFN_vkVoidFunction {
+    instance_proc_addr().and_then(|get_instance_proc_addr| get_instance_proc_addr(instance, name))
+}
+
+//device functions resolved per device skip the loader's dispatch trampoline
+pub(crate) unsafe fn get_device_proc_addr(
+    instance: daxa_sys::VkInstance,
+    device: daxa_sys::VkDevice,
+    name: *const os::raw::c_char,
+) -> daxa_sys::PFN_vkVoidFunction {
+    let get_device_proc_addr = mem::transmute::<_, daxa_sys::PFN_vkGetDeviceProcAddr>(
+        get_instance_proc_addr(instance, b"vkGetDeviceProcAddr\0".as_ptr().cast()),
+    );
+    get_device_proc_addr.and_then(|get_device_proc_addr| get_device_proc_addr(device, name))
+}
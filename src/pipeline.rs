@@ -1,23 +1,318 @@
 use bitflags::bitflags;
+use bytemuck::Pod;
+use std::{ffi, marker::PhantomData, mem, sync};
 
-use crate::{types, CompareOp, Format};
+use crate::reflection::{EntryPoint, ExecutionModel, ReflectError, ShaderReflection};
+use crate::{
+    command_recorder::MAX_COLOR_ATTACHMENTS, device::Device, types, types::assert_c_layout,
+    CompareOp, DaxaError, Format,
+};
 
-pub type RasterPipeline = daxa_sys::daxa_RasterPipeline;
+#[derive(Debug)]
+pub enum PipelineCreateError {
+    PushConstantTooLarge {
+        size: usize,
+        max: u32,
+    },
+    Reflection(ReflectError),
+    //the shader has no entry point of that name for the stage it is used as
+    MissingEntryPoint {
+        entry_point: String,
+        stage: ExecutionModel,
+    },
+    //the push constant type does not match the block the shader declares
+    PushConstantMismatch {
+        shader_size: u32,
+        type_size: usize,
+    },
+    //the fragment shader writes a location without a color attachment format
+    UnboundFragmentOutput {
+        location: u32,
+    },
+    //color attachment locations go up to MAX_COLOR_ATTACHMENTS - 1
+    ColorAttachmentOutOfRange {
+        location: usize,
+    },
+    Daxa(DaxaError),
+}
+
+impl std::fmt::Display for PipelineCreateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for PipelineCreateError {}
+
+//the push constant size of a pipeline always comes from its type, never from user input
+pub(crate) fn push_constant_size<P: Pod>(
+    device: &Device,
+) -> std::result::Result<u32, PipelineCreateError> {
+    let size = mem::size_of::<P>();
+    let max = device.properties().limits().maxPushConstantsSize;
+    if size > max as usize {
+        return Err(PipelineCreateError::PushConstantTooLarge { size, max });
+    }
+    Ok(size as u32)
+}
+
+//rust pads the type to its alignment, while the shader's block ends right after its last member
+fn check_push_constant_type<P: Pod>(
+    entry_point: &EntryPoint,
+) -> std::result::Result<(), PipelineCreateError> {
+    let Some(block) = &entry_point.push_constant else {
+        return Ok(());
+    };
+    let type_size = mem::size_of::<P>();
+    let align = mem::align_of::<P>();
+    let padded_size = (block.size as usize + align - 1) / align * align;
+    if type_size < block.size as usize || type_size > padded_size {
+        return Err(PipelineCreateError::PushConstantMismatch {
+            shader_size: block.size,
+            type_size,
+        });
+    }
+    Ok(())
+}
+
+fn check_shader<P: Pod>(
+    shader_info: &ShaderInfo,
+    stage: ExecutionModel,
+) -> std::result::Result<EntryPoint, PipelineCreateError> {
+    let reflection = shader_info
+        .reflect()
+        .map_err(PipelineCreateError::Reflection)?;
+    let name = shader_info.entry_point.as_bytes();
+    let Some(entry_point) = reflection.entry_points().iter().find(|entry_point| {
+        entry_point.name.as_bytes() == name && entry_point.execution_model == stage
+    }) else {
+        return Err(PipelineCreateError::MissingEntryPoint {
+            entry_point: String::from_utf8_lossy(name).into_owned(),
+            stage,
+        });
+    };
+    check_push_constant_type::<P>(entry_point)?;
+    Ok(entry_point.clone())
+}
+
+struct RasterPipelineHandle {
+    handle: daxa_sys::daxa_RasterPipeline,
+    _device: Device,
+}
+
+impl Drop for RasterPipelineHandle {
+    fn drop(&mut self) {
+        unsafe {
+            daxa_sys::daxa_raster_pipeline_dec_refcnt(self.handle);
+        }
+    }
+}
+
+//P is the push constant type, recorders only accept pushes of it while this pipeline is bound
+pub struct RasterPipeline<P: Pod = ()> {
+    inner: sync::Arc<RasterPipelineHandle>,
+    marker: PhantomData<P>,
+}
+
+impl<P: Pod> Clone for RasterPipeline<P> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<P: Pod> RasterPipeline<P> {
+    pub(crate) fn from_raw(device: Device, handle: daxa_sys::daxa_RasterPipeline) -> Self {
+        Self {
+            inner: sync::Arc::new(RasterPipelineHandle {
+                handle,
+                _device: device,
+            }),
+            marker: PhantomData,
+        }
+    }
 
+    pub(crate) fn handle(&self) -> daxa_sys::daxa_RasterPipeline {
+        self.inner.handle
+    }
+}
+
+struct ComputePipelineHandle {
+    handle: daxa_sys::daxa_ComputePipeline,
+    _device: Device,
+}
+
+impl Drop for ComputePipelineHandle {
+    fn drop(&mut self) {
+        unsafe {
+            daxa_sys::daxa_compute_pipeline_dec_refcnt(self.handle);
+        }
+    }
+}
+
+pub struct ComputePipeline<P: Pod = ()> {
+    inner: sync::Arc<ComputePipelineHandle>,
+    marker: PhantomData<P>,
+}
+
+impl<P: Pod> Clone for ComputePipeline<P> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<P: Pod> ComputePipeline<P> {
+    pub(crate) fn from_raw(device: Device, handle: daxa_sys::daxa_ComputePipeline) -> Self {
+        Self {
+            inner: sync::Arc::new(ComputePipelineHandle {
+                handle,
+                _device: device,
+            }),
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn handle(&self) -> daxa_sys::daxa_ComputePipeline {
+        self.inner.handle
+    }
+}
+
+const SPIRV_MAGIC: u32 = 0x0723_0203;
+//magic, version, generator, id bound and schema
+pub(crate) const SPIRV_HEADER_WORDS: usize = 5;
+//the newest spir-v vulkan 1.3 consumes
+const SPIRV_MAX_MINOR_VERSION: u8 = 6;
+
+#[derive(Debug)]
+pub enum SpirvError {
+    //the byte length is not a multiple of 4
+    InvalidLength(usize),
+    //byte code has to be 4 byte aligned, copy it into a Vec<u32> first
+    Misaligned,
+    //fewer words than the spir-v header
+    TooShort(usize),
+    InvalidMagic(u32),
+    //the module was written on a host of the other endianness
+    WrongEndianness,
+    UnsupportedVersion { major: u8, minor: u8 },
+}
+
+impl std::fmt::Display for SpirvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for SpirvError {}
+
+//validated spir-v, the header is checked on construction so broken modules never reach the driver
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct ShaderInfo<'a> {
     byte_code: *const u32,
+    //in words, not bytes
     byte_code_size: usize,
     entry_point: types::StringView<'a>,
+    //this does not affect size or alignment, only lifetime
+    marker: PhantomData<&'a [u32]>,
+}
+
+impl<'a> ShaderInfo<'a> {
+    pub fn from_words(byte_code: &'a [u32]) -> std::result::Result<Self, SpirvError> {
+        if byte_code.len() < SPIRV_HEADER_WORDS {
+            return Err(SpirvError::TooShort(byte_code.len()));
+        }
+        match byte_code[0] {
+            SPIRV_MAGIC => {}
+            magic if magic.swap_bytes() == SPIRV_MAGIC => return Err(SpirvError::WrongEndianness),
+            magic => return Err(SpirvError::InvalidMagic(magic)),
+        }
+        //0x00MMmm00, major and minor in the middle bytes
+        let [low, minor, major, high] = byte_code[1].to_le_bytes();
+        if low != 0 || high != 0 || major != 1 || minor > SPIRV_MAX_MINOR_VERSION {
+            return Err(SpirvError::UnsupportedVersion { major, minor });
+        }
+        Ok(Self::from_validated_words(byte_code))
+    }
+
+    //for byte code that already passed from_words once
+    pub(crate) fn from_validated_words(byte_code: &'a [u32]) -> Self {
+        Self {
+            byte_code: byte_code.as_ptr(),
+            byte_code_size: byte_code.len(),
+            entry_point: types::StringView::from(
+                ffi::CStr::from_bytes_with_nul(b"main\0").unwrap(),
+            ),
+            marker: PhantomData,
+        }
+    }
+
+    //for modules loaded from files or include_bytes!, which give no alignment guarantee
+    pub fn from_bytes(byte_code: &'a [u8]) -> std::result::Result<Self, SpirvError> {
+        match bytemuck::try_cast_slice(byte_code) {
+            Ok(words) => Self::from_words(words),
+            Err(bytemuck::PodCastError::OutputSliceWouldHaveSlop) => {
+                Err(SpirvError::InvalidLength(byte_code.len()))
+            }
+            Err(_) => Err(SpirvError::Misaligned),
+        }
+    }
+
+    pub fn with_entry_point(mut self, entry_point: &'a ffi::CStr) -> Self {
+        self.entry_point = types::StringView::from(entry_point);
+        self
+    }
+
+    pub fn byte_code(&self) -> &'a [u32] {
+        unsafe { std::slice::from_raw_parts(self.byte_code, self.byte_code_size) }
+    }
+
+    pub fn reflect(&self) -> std::result::Result<ShaderReflection, ReflectError> {
+        ShaderReflection::new(self.byte_code())
+    }
+
+    pub(crate) fn as_c(&self) -> daxa_sys::daxa_ShaderInfo {
+        unsafe { mem::transmute_copy(self) }
+    }
+}
+
+pub struct ComputePipelineInfo<'a> {
+    pub shader_info: ShaderInfo<'a>,
+    pub name: String,
+}
+
+impl ComputePipelineInfo<'_> {
+    //the push constant size comes from the pipeline's push constant type, see push_constant_size
+    pub(crate) fn as_c_info(
+        &self,
+        push_constant_size: u32,
+    ) -> std::result::Result<types::CInfo<daxa_sys::daxa_ComputePipelineInfo>, types::InvalidName>
+    {
+        types::CInfo::new(&self.name, |name| daxa_sys::daxa_ComputePipelineInfo {
+            shader_info: self.shader_info.as_c(),
+            push_constant_size,
+            name,
+        })
+    }
+
+    //catches what the driver would only report as validation errors or undefined behaviour
+    pub(crate) fn check_shader<P: Pod>(&self) -> std::result::Result<(), PipelineCreateError> {
+        check_shader::<P>(&self.shader_info, ExecutionModel::GLCompute)?;
+        Ok(())
+    }
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct DepthTestInfo {
-    depth_attachment_format: types::Format,
-    enable_depth_write: bool,
-    depth_test_compare_op: CompareOp,
-    min_depth_bounds: f32,
-    max_depth_bounds: f32,
+    pub depth_attachment_format: types::Format,
+    pub enable_depth_write: bool,
+    pub depth_test_compare_op: CompareOp,
+    pub min_depth_bounds: f32,
+    pub max_depth_bounds: f32,
 }
 
 impl Default for DepthTestInfo {
@@ -33,12 +328,24 @@ impl Default for DepthTestInfo {
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct RenderAttachment {
-    format: types::Format,
-    blend: types::Option<BlendInfo>,
+    pub format: types::Format,
+    pub blend: types::Option<BlendInfo>,
+}
+
+//an undefined format leaves the location unbound
+impl Default for RenderAttachment {
+    fn default() -> Self {
+        Self {
+            format: Format::UNDEFINED,
+            blend: Default::default(),
+        }
+    }
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BlendFactor {
     Zero = daxa_sys::VkBlendFactor_VK_BLEND_FACTOR_ZERO,
     One = daxa_sys::VkBlendFactor_VK_BLEND_FACTOR_ONE,
@@ -62,6 +369,7 @@ pub enum BlendFactor {
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BlendOp {
     Add = daxa_sys::VkBlendOp_VK_BLEND_OP_ADD,
     Subtract = daxa_sys::VkBlendOp_VK_BLEND_OP_SUBTRACT,
@@ -127,14 +435,15 @@ bitflags! {
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct BlendInfo {
-    src_color_blend_factor: BlendFactor,
-    dst_color_blend_factor: BlendFactor,
-    color_blend_op: BlendOp,
-    src_alpha_blend_factor: BlendFactor,
-    dst_alpha_blend_factor: BlendFactor,
-    alpha_blend_op: BlendOp,
-    color_write_mask: ColorComponentFlags,
+    pub src_color_blend_factor: BlendFactor,
+    pub dst_color_blend_factor: BlendFactor,
+    pub color_blend_op: BlendOp,
+    pub src_alpha_blend_factor: BlendFactor,
+    pub dst_alpha_blend_factor: BlendFactor,
+    pub alpha_blend_op: BlendOp,
+    pub color_write_mask: ColorComponentFlags,
 }
 
 impl Default for BlendInfo {
@@ -155,6 +464,7 @@ impl Default for BlendInfo {
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TesselationDomainOrigin {
     UpperLeft = daxa_sys::VkTessellationDomainOrigin_VK_TESSELLATION_DOMAIN_ORIGIN_UPPER_LEFT,
     LowerLeft = daxa_sys::VkTessellationDomainOrigin_VK_TESSELLATION_DOMAIN_ORIGIN_LOWER_LEFT,
@@ -165,12 +475,14 @@ pub enum TesselationDomainOrigin {
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct TesselationInfo {
-    control_points: u32,
-    origin: TesselationDomainOrigin,
+    pub control_points: u32,
+    pub origin: TesselationDomainOrigin,
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PrimitiveTopology {
     PointList = daxa_sys::VkPrimitiveTopology_VK_PRIMITIVE_TOPOLOGY_POINT_LIST,
     LineList = daxa_sys::VkPrimitiveTopology_VK_PRIMITIVE_TOPOLOGY_LINE_LIST,
@@ -190,6 +502,7 @@ pub enum PrimitiveTopology {
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PolygonMode {
     Fill = daxa_sys::VkPolygonMode_VK_POLYGON_MODE_FILL,
     Line = daxa_sys::VkPolygonMode_VK_POLYGON_MODE_LINE,
@@ -208,12 +521,14 @@ bitflags! {
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FrontFace {
     CounterClockwise = daxa_sys::VkFrontFace_VK_FRONT_FACE_COUNTER_CLOCKWISE,
     Clockwise = daxa_sys::VkFrontFace_VK_FRONT_FACE_CLOCKWISE,
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ConsevativeRasterizationModeEXT {
     DisabledExt = daxa_sys::VkConservativeRasterizationModeEXT_VK_CONSERVATIVE_RASTERIZATION_MODE_DISABLED_EXT,
     OverestimateExt = daxa_sys::VkConservativeRasterizationModeEXT_VK_CONSERVATIVE_RASTERIZATION_MODE_OVERESTIMATE_EXT,
@@ -221,26 +536,28 @@ pub enum ConsevativeRasterizationModeEXT {
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct ConsevativeRasterInfo {
-    mode: ConsevativeRasterizationModeEXT,
-    size: f32,
+    pub mode: ConsevativeRasterizationModeEXT,
+    pub size: f32,
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct RasterizerInfo {
-    primitive_topology: PrimitiveTopology,
-    primitive_restart_enable: bool,
-    polygon_mode: PolygonMode,
-    face_culling: CullModeFlags,
-    front_face_winding: FrontFace,
-    depth_clamp_enable: bool,
-    rasterizer_discard_enable: bool,
-    depth_bias_enable: bool,
-    depth_bias_constant_factor: f32,
-    depth_bias_clamp: f32,
-    depth_bias_slope_factor: f32,
-    line_width: f32,
-    conservative_raster_info: types::Option<ConsevativeRasterInfo>,
+    pub primitive_topology: PrimitiveTopology,
+    pub primitive_restart_enable: bool,
+    pub polygon_mode: PolygonMode,
+    pub face_culling: CullModeFlags,
+    pub front_face_winding: FrontFace,
+    pub depth_clamp_enable: bool,
+    pub rasterizer_discard_enable: bool,
+    pub depth_bias_enable: bool,
+    pub depth_bias_constant_factor: f32,
+    pub depth_bias_clamp: f32,
+    pub depth_bias_slope_factor: f32,
+    pub line_width: f32,
+    pub conservative_raster_info: types::Option<ConsevativeRasterInfo>,
 }
 
 impl Default for RasterizerInfo {
@@ -249,7 +566,7 @@ impl Default for RasterizerInfo {
             primitive_topology: PrimitiveTopology::TriangleList,
             primitive_restart_enable: false,
             polygon_mode: PolygonMode::Fill,
-            face_culling: CullModeFlags::None,
+            face_culling: CullModeFlags::NONE,
             front_face_winding: FrontFace::Clockwise,
             depth_clamp_enable: false,
             rasterizer_discard_enable: false,
@@ -263,18 +580,166 @@ impl Default for RasterizerInfo {
     }
 }
 
-#[repr(C)]
+//the stages a raster pipeline has a shader slot for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RasterStage {
+    Task,
+    Mesh,
+    Vertex,
+    TessellationControl,
+    TessellationEvaluation,
+    Fragment,
+}
+
+impl RasterStage {
+    pub fn execution_model(self) -> ExecutionModel {
+        match self {
+            RasterStage::Task => ExecutionModel::Task,
+            RasterStage::Mesh => ExecutionModel::Mesh,
+            RasterStage::Vertex => ExecutionModel::Vertex,
+            RasterStage::TessellationControl => ExecutionModel::TessellationControl,
+            RasterStage::TessellationEvaluation => ExecutionModel::TessellationEvaluation,
+            RasterStage::Fragment => ExecutionModel::Fragment,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
 pub struct RasterPipelineInfo<'a> {
+    pub mesh_shader_info: std::option::Option<ShaderInfo<'a>>,
+    pub vertex_shader_info: std::option::Option<ShaderInfo<'a>>,
+    pub tesselation_control_shader_info: std::option::Option<ShaderInfo<'a>>,
+    pub tesselation_evaluation_shader_info: std::option::Option<ShaderInfo<'a>>,
+    pub fragment_shader_info: std::option::Option<ShaderInfo<'a>>,
+    pub task_shader_info: std::option::Option<ShaderInfo<'a>>,
+    //indexed by fragment output location
+    pub color_attachments: [RenderAttachment; MAX_COLOR_ATTACHMENTS],
+    pub depth_test: std::option::Option<DepthTestInfo>,
+    pub tesselation: std::option::Option<TesselationInfo>,
+    pub raster: RasterizerInfo,
+    pub name: String,
+}
+
+//the daxa_RasterPipelineInfo layout, the info above only differs in owning its name
+#[repr(C)]
+struct CRasterPipelineInfo<'a> {
     mesh_shader_info: types::Option<ShaderInfo<'a>>,
     vertex_shader_info: types::Option<ShaderInfo<'a>>,
     tesselation_control_shader_info: types::Option<ShaderInfo<'a>>,
     tesselation_evaluation_shader_info: types::Option<ShaderInfo<'a>>,
     fragment_shader_info: types::Option<ShaderInfo<'a>>,
     task_shader_info: types::Option<ShaderInfo<'a>>,
-    color_attachments: [RenderAttachment; 8],
+    color_attachments: [RenderAttachment; MAX_COLOR_ATTACHMENTS],
     depth_test: types::Option<DepthTestInfo>,
     tesselation: types::Option<TesselationInfo>,
     raster: RasterizerInfo,
     push_constant_size: u32,
-    name: types::StringView<'a>,
+    name: daxa_sys::daxa_StringView,
+}
+
+impl<'a> RasterPipelineInfo<'a> {
+    //for filling the stages from a list, like the pipeline manager does
+    pub fn with_shader(mut self, stage: RasterStage, shader_info: ShaderInfo<'a>) -> Self {
+        let slot = match stage {
+            RasterStage::Task => &mut self.task_shader_info,
+            RasterStage::Mesh => &mut self.mesh_shader_info,
+            RasterStage::Vertex => &mut self.vertex_shader_info,
+            RasterStage::TessellationControl => &mut self.tesselation_control_shader_info,
+            RasterStage::TessellationEvaluation => &mut self.tesselation_evaluation_shader_info,
+            RasterStage::Fragment => &mut self.fragment_shader_info,
+        };
+        *slot = Some(shader_info);
+        self
+    }
+
+    pub fn with_color_attachment(
+        mut self,
+        location: usize,
+        format: Format,
+        blend: std::option::Option<BlendInfo>,
+    ) -> std::result::Result<Self, PipelineCreateError> {
+        let Some(attachment) = self.color_attachments.get_mut(location) else {
+            return Err(PipelineCreateError::ColorAttachmentOutOfRange { location });
+        };
+        *attachment = RenderAttachment {
+            format,
+            blend: blend.into(),
+        };
+        Ok(self)
+    }
+
+    //like ComputePipelineInfo, the push constant size comes from the pipeline's push constant type
+    pub(crate) fn as_c_info(
+        &self,
+        push_constant_size: u32,
+    ) -> std::result::Result<types::CInfo<daxa_sys::daxa_RasterPipelineInfo>, types::InvalidName>
+    {
+        types::CInfo::new(&self.name, |name| unsafe {
+            mem::transmute::<CRasterPipelineInfo, daxa_sys::daxa_RasterPipelineInfo>(
+                CRasterPipelineInfo {
+                    mesh_shader_info: self.mesh_shader_info.into(),
+                    vertex_shader_info: self.vertex_shader_info.into(),
+                    tesselation_control_shader_info: self.tesselation_control_shader_info.into(),
+                    tesselation_evaluation_shader_info: self
+                        .tesselation_evaluation_shader_info
+                        .into(),
+                    fragment_shader_info: self.fragment_shader_info.into(),
+                    task_shader_info: self.task_shader_info.into(),
+                    color_attachments: self.color_attachments,
+                    depth_test: self.depth_test.into(),
+                    tesselation: self.tesselation.into(),
+                    raster: self.raster,
+                    push_constant_size,
+                    name,
+                },
+            )
+        })
+    }
+
+    pub(crate) fn check_shaders<P: Pod>(&self) -> std::result::Result<(), PipelineCreateError> {
+        let stages = [
+            (&self.task_shader_info, RasterStage::Task),
+            (&self.mesh_shader_info, RasterStage::Mesh),
+            (&self.vertex_shader_info, RasterStage::Vertex),
+            (
+                &self.tesselation_control_shader_info,
+                RasterStage::TessellationControl,
+            ),
+            (
+                &self.tesselation_evaluation_shader_info,
+                RasterStage::TessellationEvaluation,
+            ),
+            (&self.fragment_shader_info, RasterStage::Fragment),
+        ];
+        for (shader_info, stage) in stages {
+            let Some(shader_info) = shader_info else {
+                continue;
+            };
+            let entry_point = check_shader::<P>(shader_info, stage.execution_model())?;
+            for output in &entry_point.fragment_outputs {
+                let bound = self
+                    .color_attachments
+                    .get(output.location as usize)
+                    .map_or(false, |attachment| attachment.format != Format::UNDEFINED);
+                if !bound {
+                    return Err(PipelineCreateError::UnboundFragmentOutput {
+                        location: output.location,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
 }
+
+assert_c_layout!(ShaderInfo<'static>, daxa_sys::daxa_ShaderInfo);
+assert_c_layout!(DepthTestInfo, daxa_sys::daxa_DepthTestInfo);
+assert_c_layout!(RenderAttachment, daxa_sys::daxa_RenderAttachment);
+assert_c_layout!(BlendInfo, daxa_sys::daxa_BlendInfo);
+assert_c_layout!(TesselationInfo, daxa_sys::daxa_TesselationInfo);
+assert_c_layout!(ConsevativeRasterInfo, daxa_sys::daxa_ConservativeRasterInfo);
+assert_c_layout!(RasterizerInfo, daxa_sys::daxa_RasterizerInfo);
+assert_c_layout!(
+    CRasterPipelineInfo<'static>,
+    daxa_sys::daxa_RasterPipelineInfo
+);
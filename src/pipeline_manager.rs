@@ -0,0 +1,309 @@
+use bytemuck::Pod;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync;
+use std::time;
+
+use crate::device::Device;
+use crate::pipeline::{
+    ComputePipeline, ComputePipelineInfo, PipelineCreateError, RasterPipeline, RasterPipelineInfo,
+    RasterStage,
+};
+use crate::reflection::ExecutionModel;
+use crate::shader_compiler::{
+    CompiledShader, ShaderCompileError, ShaderCompileOptions, ShaderCompiler,
+};
+
+#[derive(Debug)]
+pub enum PipelineManagerError {
+    //pipeline names identify pipelines in reports, so they have to be unique
+    DuplicateName(String),
+    Compile {
+        pipeline: String,
+        path: PathBuf,
+        error: ShaderCompileError,
+    },
+    Create {
+        pipeline: String,
+        error: PipelineCreateError,
+    },
+}
+
+impl std::fmt::Display for PipelineManagerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for PipelineManagerError {}
+
+//stays valid across reloads, hand it to the renderer instead of the pipeline itself
+pub struct ManagedComputePipeline<P: Pod = ()> {
+    current: sync::Arc<sync::RwLock<ComputePipeline<P>>>,
+}
+
+impl<P: Pod> Clone for ManagedComputePipeline<P> {
+    fn clone(&self) -> Self {
+        Self {
+            current: self.current.clone(),
+        }
+    }
+}
+
+impl<P: Pod> ManagedComputePipeline<P> {
+    //the latest pipeline that built, recorders keep it alive while it is in use
+    pub fn get(&self) -> ComputePipeline<P> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+pub struct ManagedRasterPipeline<P: Pod = ()> {
+    current: sync::Arc<sync::RwLock<RasterPipeline<P>>>,
+}
+
+impl<P: Pod> Clone for ManagedRasterPipeline<P> {
+    fn clone(&self) -> Self {
+        Self {
+            current: self.current.clone(),
+        }
+    }
+}
+
+impl<P: Pod> ManagedRasterPipeline<P> {
+    pub fn get(&self) -> RasterPipeline<P> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+//the template's shaders are replaced by the compiled stages, everything else is kept
+pub struct RasterPipelineSources {
+    pub stages: Vec<(RasterStage, PathBuf)>,
+    pub template: RasterPipelineInfo<'static>,
+}
+
+#[derive(Debug, Default)]
+pub struct ReloadReport {
+    pub reloaded: Vec<String>,
+    //the pipelines named in here keep running their last working version
+    pub errors: Vec<PipelineManagerError>,
+}
+
+//files with the modification time they had when their pipeline was last built
+type Watched = Vec<(PathBuf, Option<time::SystemTime>)>;
+
+type Rebuild = Box<dyn FnMut(&Device, &[CompiledShader]) -> Result<(), PipelineCreateError>>;
+
+struct ManagedPipeline {
+    name: String,
+    stages: Vec<(ExecutionModel, PathBuf)>,
+    //sources and everything they include
+    watched: Watched,
+    rebuild: Rebuild,
+}
+
+fn modified(path: &Path) -> Option<time::SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+//owns named pipelines built from shader files and rebuilds them when any of their files change
+pub struct PipelineManager {
+    device: Device,
+    compiler: ShaderCompiler,
+    options: ShaderCompileOptions,
+    pipelines: Vec<ManagedPipeline>,
+}
+
+impl PipelineManager {
+    //the options apply to every shader of every pipeline
+    pub fn new(
+        device: &Device,
+        options: ShaderCompileOptions,
+    ) -> std::result::Result<Self, ShaderCompileError> {
+        Ok(Self {
+            device: device.clone(),
+            compiler: ShaderCompiler::new()?,
+            options,
+            pipelines: Vec::new(),
+        })
+    }
+
+    pub fn add_compute_pipeline<P: Pod>(
+        &mut self,
+        name: impl Into<String>,
+        path: impl Into<PathBuf>,
+    ) -> std::result::Result<ManagedComputePipeline<P>, PipelineManagerError> {
+        let name = name.into();
+        let stages = vec![(ExecutionModel::GLCompute, path.into())];
+        let create = {
+            let name = name.clone();
+            move |device: &Device, shaders: &[CompiledShader]| {
+                device.create_compute_pipeline::<P>(&ComputePipelineInfo {
+                    shader_info: shaders[0].shader_info(),
+                    name: name.clone(),
+                })
+            }
+        };
+        let (pipeline, watched) = self.build(&name, &stages, &create)?;
+
+        let managed = ManagedComputePipeline {
+            current: sync::Arc::new(sync::RwLock::new(pipeline)),
+        };
+        let current = managed.current.clone();
+        self.pipelines.push(ManagedPipeline {
+            name,
+            stages,
+            watched,
+            rebuild: Box::new(move |device, shaders| {
+                let pipeline = create(device, shaders)?;
+                *current.write().unwrap_or_else(|e| e.into_inner()) = pipeline;
+                Ok(())
+            }),
+        });
+        Ok(managed)
+    }
+
+    pub fn add_raster_pipeline<P: Pod>(
+        &mut self,
+        name: impl Into<String>,
+        sources: RasterPipelineSources,
+    ) -> std::result::Result<ManagedRasterPipeline<P>, PipelineManagerError> {
+        let name = name.into();
+        let RasterPipelineSources { stages, template } = sources;
+        let create = {
+            let name = name.clone();
+            let stages = stages.iter().map(|(stage, _)| *stage).collect::<Vec<_>>();
+            move |device: &Device, shaders: &[CompiledShader]| {
+                let info = stages.iter().zip(shaders).fold(
+                    RasterPipelineInfo {
+                        name: name.clone(),
+                        ..template.clone()
+                    },
+                    |info, (stage, shader)| info.with_shader(*stage, shader.shader_info()),
+                );
+                device.create_raster_pipeline::<P>(&info)
+            }
+        };
+        let stages = stages
+            .into_iter()
+            .map(|(stage, path)| (stage.execution_model(), path))
+            .collect::<Vec<_>>();
+        let (pipeline, watched) = self.build(&name, &stages, &create)?;
+
+        let managed = ManagedRasterPipeline {
+            current: sync::Arc::new(sync::RwLock::new(pipeline)),
+        };
+        let current = managed.current.clone();
+        self.pipelines.push(ManagedPipeline {
+            name,
+            stages,
+            watched,
+            rebuild: Box::new(move |device, shaders| {
+                let pipeline = create(device, shaders)?;
+                *current.write().unwrap_or_else(|e| e.into_inner()) = pipeline;
+                Ok(())
+            }),
+        });
+        Ok(managed)
+    }
+
+    //checks every watched file and rebuilds the pipelines that use a changed one, call it once per frame
+    pub fn reload(&mut self) -> ReloadReport {
+        let mut report = ReloadReport::default();
+        for pipeline in &mut self.pipelines {
+            let changed = pipeline
+                .watched
+                .iter()
+                .any(|(path, modified_at)| modified(path) != *modified_at);
+            if !changed {
+                continue;
+            }
+
+            let started = pipeline
+                .watched
+                .iter()
+                .map(|(path, _)| (path.clone(), modified(path)))
+                .collect::<Vec<_>>();
+            let result = match compile(
+                &self.compiler,
+                &self.options,
+                &pipeline.name,
+                &pipeline.stages,
+            ) {
+                Ok((shaders, watched)) => (pipeline.rebuild)(&self.device, &shaders)
+                    .map(|()| watched)
+                    .map_err(|error| PipelineManagerError::Create {
+                        pipeline: pipeline.name.clone(),
+                        error,
+                    }),
+                Err(error) => Err(error),
+            };
+            match result {
+                Ok(watched) => {
+                    pipeline.watched = watched;
+                    report.reloaded.push(pipeline.name.clone());
+                }
+                //retried once a file changes again, the includes of the last working build stay watched
+                Err(error) => {
+                    pipeline.watched = started;
+                    report.errors.push(error);
+                }
+            }
+        }
+        report
+    }
+
+    fn build<T>(
+        &self,
+        name: &str,
+        stages: &[(ExecutionModel, PathBuf)],
+        create: &impl Fn(&Device, &[CompiledShader]) -> Result<T, PipelineCreateError>,
+    ) -> std::result::Result<(T, Watched), PipelineManagerError> {
+        if self.pipelines.iter().any(|pipeline| pipeline.name == name) {
+            return Err(PipelineManagerError::DuplicateName(name.to_string()));
+        }
+        let (shaders, watched) = compile(&self.compiler, &self.options, name, stages)?;
+        let pipeline =
+            create(&self.device, &shaders).map_err(|error| PipelineManagerError::Create {
+                pipeline: name.to_string(),
+                error,
+            })?;
+        Ok((pipeline, watched))
+    }
+}
+
+//sources are stamped before compiling, so edits made during a compile trigger another reload
+fn compile(
+    compiler: &ShaderCompiler,
+    options: &ShaderCompileOptions,
+    name: &str,
+    stages: &[(ExecutionModel, PathBuf)],
+) -> std::result::Result<(Vec<CompiledShader>, Watched), PipelineManagerError> {
+    let mut shaders = Vec::with_capacity(stages.len());
+    let mut watched = Vec::new();
+    for (stage, path) in stages {
+        watched.push((path.clone(), modified(path)));
+        let shader = compiler
+            .compile_file(path, *stage, options)
+            .map_err(|error| PipelineManagerError::Compile {
+                pipeline: name.to_string(),
+                path: path.clone(),
+                error,
+            })?;
+        shaders.push(shader);
+    }
+    for shader in &shaders {
+        for include in shader.includes() {
+            if watched.iter().all(|(path, _)| path != include) {
+                watched.push((include.clone(), modified(include)));
+            }
+        }
+    }
+    Ok((shaders, watched))
+}
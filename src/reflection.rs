@@ -0,0 +1,537 @@
+use std::collections::HashMap;
+
+use crate::command_recorder::DispatchInfo;
+use crate::pipeline::SPIRV_HEADER_WORDS;
+
+//opcodes and enumerants of the spir-v specification, only the ones reflection looks at
+const OP_MEMBER_NAME: u32 = 6;
+const OP_ENTRY_POINT: u32 = 15;
+const OP_EXECUTION_MODE: u32 = 16;
+const OP_TYPE_BOOL: u32 = 20;
+const OP_TYPE_INT: u32 = 21;
+const OP_TYPE_FLOAT: u32 = 22;
+const OP_TYPE_VECTOR: u32 = 23;
+const OP_TYPE_MATRIX: u32 = 24;
+const OP_TYPE_ARRAY: u32 = 28;
+const OP_TYPE_RUNTIME_ARRAY: u32 = 29;
+const OP_TYPE_STRUCT: u32 = 30;
+const OP_TYPE_POINTER: u32 = 32;
+const OP_CONSTANT: u32 = 43;
+const OP_CONSTANT_COMPOSITE: u32 = 44;
+const OP_SPEC_CONSTANT: u32 = 50;
+const OP_SPEC_CONSTANT_COMPOSITE: u32 = 51;
+const OP_VARIABLE: u32 = 59;
+const OP_DECORATE: u32 = 71;
+const OP_MEMBER_DECORATE: u32 = 72;
+
+const DECORATION_ARRAY_STRIDE: u32 = 6;
+const DECORATION_MATRIX_STRIDE: u32 = 7;
+const DECORATION_BUILT_IN: u32 = 11;
+const DECORATION_LOCATION: u32 = 30;
+const DECORATION_OFFSET: u32 = 35;
+
+const BUILT_IN_WORKGROUP_SIZE: u32 = 25;
+const EXECUTION_MODE_LOCAL_SIZE: u32 = 17;
+const STORAGE_CLASS_OUTPUT: u32 = 3;
+const STORAGE_CLASS_PUSH_CONSTANT: u32 = 9;
+
+//physical storage buffer pointers, the way daxa shaders receive buffer addresses
+const POINTER_SIZE: u32 = 8;
+
+#[derive(Debug)]
+pub enum ReflectError {
+    //the instruction at this word offset is cut off or misses operands
+    MalformedInstruction(usize),
+    //an instruction refers to an id that is never defined
+    UndefinedId(u32),
+    //the id is defined, but not as the kind of type the instruction requires
+    UnexpectedType(u32),
+    //push constant blocks can not contain runtime arrays
+    UnsizedPushConstant,
+}
+
+impl std::fmt::Display for ReflectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for ReflectError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionModel {
+    Vertex,
+    TessellationControl,
+    TessellationEvaluation,
+    Geometry,
+    Fragment,
+    GLCompute,
+    //the nv and ext variants are not told apart
+    Task,
+    Mesh,
+    //ray tracing and kernel stages, with their raw spir-v value
+    Other(u32),
+}
+
+impl ExecutionModel {
+    fn from_raw(model: u32) -> Self {
+        match model {
+            0 => Self::Vertex,
+            1 => Self::TessellationControl,
+            2 => Self::TessellationEvaluation,
+            3 => Self::Geometry,
+            4 => Self::Fragment,
+            5 => Self::GLCompute,
+            5267 | 5364 => Self::Task,
+            5268 | 5365 => Self::Mesh,
+            model => Self::Other(model),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComponentType {
+    Float,
+    Sint,
+    Uint,
+}
+
+#[derive(Clone, Debug)]
+pub struct FragmentOutput {
+    pub location: u32,
+    pub component_type: ComponentType,
+    pub component_count: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct PushConstantMember {
+    //only present if the module was compiled with debug names
+    pub name: Option<String>,
+    pub offset: u32,
+    pub size: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct PushConstantBlock {
+    //ends right after the last member, without padding to the block's alignment
+    pub size: u32,
+    pub members: Vec<PushConstantMember>,
+}
+
+#[derive(Clone, Debug)]
+pub struct EntryPoint {
+    pub name: String,
+    pub execution_model: ExecutionModel,
+    //only compute, task and mesh shaders have one
+    pub local_size: Option<[u32; 3]>,
+    pub push_constant: Option<PushConstantBlock>,
+    //sorted by location, empty for all but fragment shaders
+    pub fragment_outputs: Vec<FragmentOutput>,
+}
+
+impl EntryPoint {
+    //the workgroups needed so every one of the invocations runs at least once
+    pub fn workgroup_count(&self, invocations: [u32; 3]) -> Option<DispatchInfo> {
+        let [x, y, z] = self.local_size?;
+        let count = |invocations: u32, local_size: u32| {
+            invocations / local_size + (invocations % local_size != 0) as u32
+        };
+        Some(DispatchInfo {
+            x: count(invocations[0], x),
+            y: count(invocations[1], y),
+            z: count(invocations[2], z),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ShaderReflection {
+    entry_points: Vec<EntryPoint>,
+}
+
+impl ShaderReflection {
+    //expects a module whose header was already validated, see ShaderInfo::reflect
+    pub(crate) fn new(words: &[u32]) -> std::result::Result<Self, ReflectError> {
+        let mut module = Module::default();
+        let mut offset = SPIRV_HEADER_WORDS;
+        while offset < words.len() {
+            let word_count = (words[offset] >> 16) as usize;
+            let opcode = words[offset] & 0xffff;
+            if word_count == 0 || offset + word_count > words.len() {
+                return Err(ReflectError::MalformedInstruction(offset));
+            }
+            module
+                .record(opcode, &words[offset + 1..offset + word_count])
+                .ok_or(ReflectError::MalformedInstruction(offset))?;
+            offset += word_count;
+        }
+
+        //before 1.4 the interface of an entry point only lists its inputs and outputs
+        let [_, minor, _, _] = words[1].to_le_bytes();
+        let interface_has_globals = minor >= 4;
+        let entry_points = module
+            .entry_points
+            .iter()
+            .map(|entry_point| module.entry_point(entry_point, interface_has_globals))
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(Self { entry_points })
+    }
+
+    pub fn entry_points(&self) -> &[EntryPoint] {
+        &self.entry_points
+    }
+
+    //the same name can be used once per execution model, this returns the first
+    pub fn entry_point(&self, name: &str) -> Option<&EntryPoint> {
+        self.entry_points
+            .iter()
+            .find(|entry_point| entry_point.name == name)
+    }
+}
+
+enum Type {
+    Bool,
+    Int { width: u32, signed: bool },
+    Float { width: u32 },
+    Vector { component: u32, count: u32 },
+    Matrix { column: u32, count: u32 },
+    //the length is the id of a constant
+    Array { element: u32, length: u32 },
+    RuntimeArray,
+    Struct { members: Vec<u32> },
+    Pointer { pointee: u32 },
+}
+
+struct Variable {
+    id: u32,
+    pointer: u32,
+    storage_class: u32,
+}
+
+struct RawEntryPoint {
+    execution_model: u32,
+    function: u32,
+    name: String,
+    interface: Vec<u32>,
+}
+
+#[derive(Default)]
+struct Module {
+    entry_points: Vec<RawEntryPoint>,
+    local_sizes: HashMap<u32, [u32; 3]>,
+    member_names: HashMap<(u32, u32), String>,
+    locations: HashMap<u32, u32>,
+    built_ins: HashMap<u32, u32>,
+    array_strides: HashMap<u32, u32>,
+    member_offsets: HashMap<(u32, u32), u32>,
+    matrix_strides: HashMap<(u32, u32), u32>,
+    types: HashMap<u32, Type>,
+    //only the low word, enough for lengths and sizes
+    constants: HashMap<u32, u32>,
+    composites: HashMap<u32, Vec<u32>>,
+    variables: Vec<Variable>,
+}
+
+//nul terminated utf-8 packed into words, returns the string and the words it took
+fn literal_string(operands: &[u32]) -> Option<(String, usize)> {
+    let mut bytes = Vec::new();
+    for (index, word) in operands.iter().enumerate() {
+        for byte in word.to_le_bytes() {
+            if byte == 0 {
+                return Some((String::from_utf8_lossy(&bytes).into_owned(), index + 1));
+            }
+            bytes.push(byte);
+        }
+    }
+    None
+}
+
+impl Module {
+    //None if the instruction has fewer operands than it requires
+    fn record(&mut self, opcode: u32, operands: &[u32]) -> Option<()> {
+        let operand = |index: usize| operands.get(index).copied();
+        match opcode {
+            OP_MEMBER_NAME => {
+                let (name, _) = literal_string(operands.get(2..)?)?;
+                self.member_names.insert((operand(0)?, operand(1)?), name);
+            }
+            OP_ENTRY_POINT => {
+                let (name, name_words) = literal_string(operands.get(2..)?)?;
+                self.entry_points.push(RawEntryPoint {
+                    execution_model: operand(0)?,
+                    function: operand(1)?,
+                    name,
+                    interface: operands[2 + name_words..].to_vec(),
+                });
+            }
+            OP_EXECUTION_MODE if operand(1)? == EXECUTION_MODE_LOCAL_SIZE => {
+                let local_size = [operand(2)?, operand(3)?, operand(4)?];
+                self.local_sizes.insert(operand(0)?, local_size);
+            }
+            OP_DECORATE => {
+                let decorations = match operand(1)? {
+                    DECORATION_LOCATION => &mut self.locations,
+                    DECORATION_BUILT_IN => &mut self.built_ins,
+                    DECORATION_ARRAY_STRIDE => &mut self.array_strides,
+                    _ => return Some(()),
+                };
+                decorations.insert(operand(0)?, operand(2)?);
+            }
+            OP_MEMBER_DECORATE => {
+                let decorations = match operand(2)? {
+                    DECORATION_OFFSET => &mut self.member_offsets,
+                    DECORATION_MATRIX_STRIDE => &mut self.matrix_strides,
+                    _ => return Some(()),
+                };
+                decorations.insert((operand(0)?, operand(1)?), operand(3)?);
+            }
+            OP_TYPE_BOOL => {
+                self.types.insert(operand(0)?, Type::Bool);
+            }
+            OP_TYPE_INT => {
+                let ty = Type::Int {
+                    width: operand(1)?,
+                    signed: operand(2)? != 0,
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_FLOAT => {
+                let ty = Type::Float { width: operand(1)? };
+                self.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_VECTOR => {
+                let ty = Type::Vector {
+                    component: operand(1)?,
+                    count: operand(2)?,
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_MATRIX => {
+                let ty = Type::Matrix {
+                    column: operand(1)?,
+                    count: operand(2)?,
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_ARRAY => {
+                let ty = Type::Array {
+                    element: operand(1)?,
+                    length: operand(2)?,
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_RUNTIME_ARRAY => {
+                self.types.insert(operand(0)?, Type::RuntimeArray);
+            }
+            OP_TYPE_STRUCT => {
+                let ty = Type::Struct {
+                    members: operands.get(1..)?.to_vec(),
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_POINTER => {
+                let ty = Type::Pointer {
+                    pointee: operand(2)?,
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            OP_CONSTANT | OP_SPEC_CONSTANT => {
+                self.constants.insert(operand(1)?, operand(2)?);
+            }
+            OP_CONSTANT_COMPOSITE | OP_SPEC_CONSTANT_COMPOSITE => {
+                self.composites
+                    .insert(operand(1)?, operands.get(2..)?.to_vec());
+            }
+            OP_VARIABLE => self.variables.push(Variable {
+                id: operand(1)?,
+                pointer: operand(0)?,
+                storage_class: operand(2)?,
+            }),
+            _ => {}
+        }
+        Some(())
+    }
+
+    fn entry_point(
+        &self,
+        raw: &RawEntryPoint,
+        interface_has_globals: bool,
+    ) -> std::result::Result<EntryPoint, ReflectError> {
+        let execution_model = ExecutionModel::from_raw(raw.execution_model);
+        let local_size = match execution_model {
+            //a WorkgroupSize built-in takes precedence over the execution mode
+            ExecutionModel::GLCompute | ExecutionModel::Task | ExecutionModel::Mesh => self
+                .workgroup_size()?
+                .or_else(|| self.local_sizes.get(&raw.function).copied()),
+            _ => None,
+        };
+
+        let mut push_constants = self
+            .variables
+            .iter()
+            .filter(|variable| variable.storage_class == STORAGE_CLASS_PUSH_CONSTANT);
+        //older modules do not say which entry point uses which block, there is at most one then
+        let push_constant = if interface_has_globals {
+            push_constants.find(|variable| raw.interface.contains(&variable.id))
+        } else {
+            push_constants.last()
+        };
+        let push_constant = push_constant
+            .map(|variable| self.push_constant_block(variable.pointer))
+            .transpose()?;
+
+        let mut fragment_outputs = Vec::new();
+        if execution_model == ExecutionModel::Fragment {
+            for variable in &self.variables {
+                if variable.storage_class != STORAGE_CLASS_OUTPUT
+                    || !raw.interface.contains(&variable.id)
+                {
+                    continue;
+                }
+                //built-ins like FragDepth have no location
+                if let Some(&location) = self.locations.get(&variable.id) {
+                    self.fragment_outputs(variable.pointer, location, &mut fragment_outputs)?;
+                }
+            }
+            fragment_outputs.sort_by_key(|output| output.location);
+        }
+
+        Ok(EntryPoint {
+            name: raw.name.clone(),
+            execution_model,
+            local_size,
+            push_constant,
+            fragment_outputs,
+        })
+    }
+
+    fn workgroup_size(&self) -> std::result::Result<Option<[u32; 3]>, ReflectError> {
+        let Some((&id, _)) = self
+            .built_ins
+            .iter()
+            .find(|&(_, &built_in)| built_in == BUILT_IN_WORKGROUP_SIZE)
+        else {
+            return Ok(None);
+        };
+        let components = self
+            .composites
+            .get(&id)
+            .ok_or(ReflectError::UndefinedId(id))?;
+        let mut size = [1; 3];
+        for (size, component) in size.iter_mut().zip(components) {
+            *size = self.constant(*component)?;
+        }
+        Ok(Some(size))
+    }
+
+    fn constant(&self, id: u32) -> std::result::Result<u32, ReflectError> {
+        self.constants
+            .get(&id)
+            .copied()
+            .ok_or(ReflectError::UndefinedId(id))
+    }
+
+    fn ty(&self, id: u32) -> std::result::Result<&Type, ReflectError> {
+        self.types.get(&id).ok_or(ReflectError::UndefinedId(id))
+    }
+
+    fn pointee(&self, pointer: u32) -> std::result::Result<u32, ReflectError> {
+        match self.ty(pointer)? {
+            Type::Pointer { pointee } => Ok(*pointee),
+            _ => Err(ReflectError::UnexpectedType(pointer)),
+        }
+    }
+
+    fn push_constant_block(
+        &self,
+        pointer: u32,
+    ) -> std::result::Result<PushConstantBlock, ReflectError> {
+        let block = self.pointee(pointer)?;
+        let Type::Struct { members } = self.ty(block)? else {
+            return Err(ReflectError::UnexpectedType(block));
+        };
+        let members = members
+            .iter()
+            .enumerate()
+            .map(|(index, &member)| -> std::result::Result<_, ReflectError> {
+                let key = (block, index as u32);
+                Ok(PushConstantMember {
+                    name: self.member_names.get(&key).cloned(),
+                    offset: self.member_offsets.get(&key).copied().unwrap_or(0),
+                    size: self.size_of(member, self.matrix_strides.get(&key).copied())?,
+                })
+            })
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(PushConstantBlock {
+            size: self.size_of(block, None)?,
+            members,
+        })
+    }
+
+    //matrix_stride comes from the member decoration of the struct containing the matrix
+    fn size_of(
+        &self,
+        id: u32,
+        matrix_stride: Option<u32>,
+    ) -> std::result::Result<u32, ReflectError> {
+        match self.ty(id)? {
+            Type::Bool => Ok(4),
+            Type::Int { width, .. } | Type::Float { width } => Ok(width / 8),
+            Type::Vector { component, count } => Ok(self.size_of(*component, None)? * count),
+            Type::Matrix { column, count } => match matrix_stride {
+                Some(stride) => Ok(stride * count),
+                None => Ok(self.size_of(*column, None)? * count),
+            },
+            Type::Array { element, length } => {
+                let length = self.constant(*length)?;
+                match self.array_strides.get(&id) {
+                    Some(stride) => Ok(stride * length),
+                    None => Ok(self.size_of(*element, matrix_stride)? * length),
+                }
+            }
+            Type::RuntimeArray => Err(ReflectError::UnsizedPushConstant),
+            Type::Struct { members } => {
+                let mut size = 0;
+                for (index, &member) in members.iter().enumerate() {
+                    let key = (id, index as u32);
+                    let offset = self.member_offsets.get(&key).copied().unwrap_or(size);
+                    let member_size =
+                        self.size_of(member, self.matrix_strides.get(&key).copied())?;
+                    size = size.max(offset + member_size);
+                }
+                Ok(size)
+            }
+            Type::Pointer { .. } => Ok(POINTER_SIZE),
+        }
+    }
+
+    //arrays of outputs take one location per element
+    fn fragment_outputs(
+        &self,
+        pointer: u32,
+        location: u32,
+        outputs: &mut Vec<FragmentOutput>,
+    ) -> std::result::Result<(), ReflectError> {
+        let mut ty = self.pointee(pointer)?;
+        let mut locations = 1;
+        if let Type::Array { element, length } = self.ty(ty)? {
+            locations = self.constant(*length)?;
+            ty = *element;
+        }
+        let (component, component_count) = match self.ty(ty)? {
+            Type::Vector { component, count } => (*component, *count),
+            _ => (ty, 1),
+        };
+        let component_type = match self.ty(component)? {
+            Type::Float { .. } => ComponentType::Float,
+            Type::Int { signed: true, .. } => ComponentType::Sint,
+            Type::Int { signed: false, .. } => ComponentType::Uint,
+            _ => return Err(ReflectError::UnexpectedType(component)),
+        };
+        outputs.extend((0..locations).map(|offset| FragmentOutput {
+            location: location + offset,
+            component_type,
+            component_count,
+        }));
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+use std::cell;
+use std::ffi;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::pipeline::{ShaderInfo, SpirvError};
+use crate::reflection::ExecutionModel;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ShaderLanguage {
+    #[default]
+    Glsl,
+    Hlsl,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OptimizationLevel {
+    #[default]
+    None,
+    Size,
+    Performance,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ShaderCompileOptions {
+    pub language: ShaderLanguage,
+    //searched in order, daxa's own headers are always searched last
+    pub include_paths: Vec<PathBuf>,
+    //name and value, an empty value defines the name as 1 like -DNAME does
+    pub defines: Vec<(String, String)>,
+    pub optimization: OptimizationLevel,
+    pub debug_info: bool,
+    pub entry_point: String,
+}
+
+impl Default for ShaderCompileOptions {
+    fn default() -> Self {
+        Self {
+            language: ShaderLanguage::Glsl,
+            include_paths: Vec::new(),
+            defines: Vec::new(),
+            optimization: OptimizationLevel::None,
+            debug_info: false,
+            entry_point: String::from("main"),
+        }
+    }
+}
+
+//one error of a failed compile, the file is the one that contains the error, not the compiled one
+#[derive(Clone, Debug)]
+pub struct ShaderDiagnostic {
+    pub file: Option<PathBuf>,
+    pub line: Option<u32>,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub enum ShaderCompileError {
+    //shaderc failed to initialize
+    CompilerUnavailable,
+    Io { path: PathBuf, error: io::Error },
+    UnsupportedStage(ExecutionModel),
+    Compile(Vec<ShaderDiagnostic>),
+    //shaderc produced something that is not a usable spir-v module
+    InvalidOutput(SpirvError),
+    Internal(String),
+}
+
+impl std::fmt::Display for ShaderCompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for ShaderCompileError {}
+
+pub struct CompiledShader {
+    spirv: Vec<u32>,
+    entry_point: ffi::CString,
+    includes: Vec<PathBuf>,
+    warnings: String,
+}
+
+impl CompiledShader {
+    pub fn spirv(&self) -> &[u32] {
+        &self.spirv
+    }
+
+    //the spir-v was validated when it was compiled
+    pub fn shader_info(&self) -> ShaderInfo<'_> {
+        ShaderInfo::from_validated_words(&self.spirv).with_entry_point(&self.entry_point)
+    }
+
+    //every file pulled in through #include, without the compiled file itself
+    pub fn includes(&self) -> &[PathBuf] {
+        &self.includes
+    }
+
+    pub fn warnings(&self) -> &str {
+        &self.warnings
+    }
+}
+
+pub struct ShaderCompiler {
+    compiler: shaderc::Compiler,
+}
+
+impl ShaderCompiler {
+    pub fn new() -> std::result::Result<Self, ShaderCompileError> {
+        let compiler = shaderc::Compiler::new().ok_or(ShaderCompileError::CompilerUnavailable)?;
+        Ok(Self { compiler })
+    }
+
+    pub fn compile_file(
+        &self,
+        path: &Path,
+        stage: ExecutionModel,
+        options: &ShaderCompileOptions,
+    ) -> std::result::Result<CompiledShader, ShaderCompileError> {
+        let source = fs::read_to_string(path).map_err(|error| ShaderCompileError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        self.compile_source(&source, path, stage, options)
+    }
+
+    //path names the source in diagnostics and is where relative includes are searched first
+    pub fn compile_source(
+        &self,
+        source: &str,
+        path: &Path,
+        stage: ExecutionModel,
+        options: &ShaderCompileOptions,
+    ) -> std::result::Result<CompiledShader, ShaderCompileError> {
+        let shader_kind = match stage {
+            ExecutionModel::Vertex => shaderc::ShaderKind::Vertex,
+            ExecutionModel::TessellationControl => shaderc::ShaderKind::TessControl,
+            ExecutionModel::TessellationEvaluation => shaderc::ShaderKind::TessEvaluation,
+            ExecutionModel::Geometry => shaderc::ShaderKind::Geometry,
+            ExecutionModel::Fragment => shaderc::ShaderKind::Fragment,
+            ExecutionModel::GLCompute => shaderc::ShaderKind::Compute,
+            ExecutionModel::Task => shaderc::ShaderKind::Task,
+            ExecutionModel::Mesh => shaderc::ShaderKind::Mesh,
+            ExecutionModel::Other(_) => return Err(ShaderCompileError::UnsupportedStage(stage)),
+        };
+
+        let include_paths = options
+            .include_paths
+            .iter()
+            .map(PathBuf::as_path)
+            .chain([Path::new(daxa_sys::SHADER_INCLUDE_DIR)])
+            .collect::<Vec<_>>();
+        let includes = cell::RefCell::new(Vec::new());
+
+        let mut c_options =
+            shaderc::CompileOptions::new().ok_or(ShaderCompileError::CompilerUnavailable)?;
+        c_options.set_target_env(
+            shaderc::TargetEnv::Vulkan,
+            shaderc::EnvVersion::Vulkan1_3 as u32,
+        );
+        c_options.set_source_language(match options.language {
+            ShaderLanguage::Glsl => shaderc::SourceLanguage::GLSL,
+            ShaderLanguage::Hlsl => shaderc::SourceLanguage::HLSL,
+        });
+        c_options.set_optimization_level(match options.optimization {
+            OptimizationLevel::None => shaderc::OptimizationLevel::Zero,
+            OptimizationLevel::Size => shaderc::OptimizationLevel::Size,
+            OptimizationLevel::Performance => shaderc::OptimizationLevel::Performance,
+        });
+        if options.debug_info {
+            c_options.set_generate_debug_info();
+        }
+        //daxa's headers only declare their shader side when these are set
+        c_options.add_macro_definition("DAXA_SHADER", Some("1"));
+        c_options.add_macro_definition(
+            "DAXA_SHADERLANG",
+            Some(match options.language {
+                ShaderLanguage::Glsl => "1",
+                ShaderLanguage::Hlsl => "2",
+            }),
+        );
+        for (name, value) in &options.defines {
+            let value = (!value.is_empty()).then_some(value.as_str());
+            c_options.add_macro_definition(name, value);
+        }
+        c_options.set_include_callback(|requested, include_type, requesting, _depth| {
+            let path = resolve_include(requested, include_type, requesting, &include_paths)
+                .ok_or_else(|| format!("{} not found in the include paths", requested))?;
+            let content = fs::read_to_string(&path)
+                .map_err(|error| format!("{}: {}", path.display(), error))?;
+            includes.borrow_mut().push(path.clone());
+            Ok(shaderc::ResolvedInclude {
+                resolved_name: path.display().to_string(),
+                content,
+            })
+        });
+
+        let artifact = self
+            .compiler
+            .compile_into_spirv(
+                source,
+                shader_kind,
+                &path.display().to_string(),
+                &options.entry_point,
+                Some(&c_options),
+            )
+            .map_err(|error| match error {
+                shaderc::Error::CompilationError(_, messages) => {
+                    ShaderCompileError::Compile(parse_diagnostics(&messages))
+                }
+                error => ShaderCompileError::Internal(error.to_string()),
+            })?;
+        drop(c_options);
+        let spirv = artifact.as_binary().to_vec();
+        ShaderInfo::from_words(&spirv).map_err(ShaderCompileError::InvalidOutput)?;
+
+        let mut includes = includes.into_inner();
+        includes.sort();
+        includes.dedup();
+        Ok(CompiledShader {
+            spirv,
+            entry_point: ffi::CString::new(options.entry_point.as_str())
+                .map_err(|error| ShaderCompileError::Internal(error.to_string()))?,
+            includes,
+            warnings: artifact.get_warning_messages(),
+        })
+    }
+}
+
+//quoted includes look next to the including file first, like c compilers do
+fn resolve_include(
+    requested: &str,
+    include_type: shaderc::IncludeType,
+    requesting: &str,
+    include_paths: &[&Path],
+) -> Option<PathBuf> {
+    let relative = match include_type {
+        shaderc::IncludeType::Relative => Path::new(requesting).parent(),
+        shaderc::IncludeType::Standard => None,
+    };
+    relative
+        .into_iter()
+        .chain(include_paths.iter().copied())
+        .map(|directory| directory.join(requested))
+        .find(|path| path.is_file())
+}
+
+//glslang reports errors as `file:line: error: message`, resolved includes name the included file
+fn parse_diagnostics(messages: &str) -> Vec<ShaderDiagnostic> {
+    let mut diagnostics = Vec::new();
+    for line in messages.lines() {
+        //skips warnings and the trailing error count
+        let Some((location, message)) = line.split_once(": error: ") else {
+            continue;
+        };
+        //rsplit, windows paths have a colon after the drive letter
+        let (file, line) = match location.rsplit_once(':') {
+            Some((file, line)) if line.parse::<u32>().is_ok() => (file, line.parse().ok()),
+            _ => (location, None),
+        };
+        diagnostics.push(ShaderDiagnostic {
+            file: Some(PathBuf::from(file)),
+            line,
+            message: message.to_string(),
+        });
+    }
+    if diagnostics.is_empty() {
+        diagnostics.push(ShaderDiagnostic {
+            file: None,
+            line: None,
+            message: messages.trim().to_string(),
+        });
+    }
+    diagnostics
+}
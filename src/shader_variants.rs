@@ -0,0 +1,326 @@
+use bytemuck::Pod;
+use std::collections::{hash_map, HashMap};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::ops;
+use std::path::{Path, PathBuf};
+
+use crate::device::Device;
+use crate::pipeline::{PipelineCreateError, RasterPipeline, RasterPipelineInfo, RasterStage};
+use crate::pipeline_manager::RasterPipelineSources;
+use crate::shader_compiler::{ShaderCompileError, ShaderCompileOptions, ShaderCompiler};
+
+#[derive(Debug)]
+pub enum ShaderVariantError {
+    //shaderc failed to initialize
+    CompilerUnavailable,
+    //the define is not one of the set's options
+    UnknownOption(String),
+    InvalidValue {
+        option: String,
+        value: String,
+    },
+    Compile {
+        path: PathBuf,
+        error: ShaderCompileError,
+    },
+    Create(PipelineCreateError),
+}
+
+impl std::fmt::Display for ShaderVariantError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for ShaderVariantError {}
+
+//a define and the values it may take, the first value is used when a variant does not set it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariantOption {
+    name: String,
+    values: Vec<String>,
+}
+
+impl VariantOption {
+    //defined as 0 or 1, off by default
+    pub fn flag(name: impl Into<String>) -> Self {
+        Self::one_of(name, ["0", "1"])
+    }
+
+    pub fn range(name: impl Into<String>, range: ops::RangeInclusive<u32>) -> Self {
+        Self::one_of(name, range.map(|value| value.to_string()))
+    }
+
+    //for enum like options, the values are used as written
+    pub fn one_of(
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        let name = name.into();
+        let values = values.into_iter().map(Into::into).collect::<Vec<_>>();
+        assert!(!values.is_empty(), "variant option {} has no values", name);
+        Self { name, values }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
+}
+
+pub struct ShaderVariantSetInfo {
+    pub name: String,
+    pub sources: RasterPipelineSources,
+    pub options: Vec<VariantOption>,
+    //shared by every variant, the option defines are added on top
+    pub compile_options: ShaderCompileOptions,
+}
+
+struct StageSource {
+    stage: RasterStage,
+    path: PathBuf,
+    text: String,
+}
+
+struct Variant<P: Pod> {
+    pipeline: RasterPipeline<P>,
+    //requested since the last evict_unused
+    used: bool,
+    includes: Includes,
+}
+
+//what a variant pulled in through #include, with the content hash it was compiled from
+type Includes = Vec<(PathBuf, Option<u64>)>;
+
+//None for files that can no longer be read, which never matches a compiled include
+fn content_hash(path: &Path) -> Option<u64> {
+    let content = fs::read(path).ok()?;
+    let mut hasher = hash_map::DefaultHasher::new();
+    content.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+//compiles every combination of option values on first use, all variants share one pipeline template
+pub struct ShaderVariantSet<P: Pod = ()> {
+    device: Device,
+    compiler: ShaderCompiler,
+    name: String,
+    stages: Vec<(RasterStage, PathBuf)>,
+    sources: Vec<StageSource>,
+    source_hash: u64,
+    template: RasterPipelineInfo<'static>,
+    options: Vec<VariantOption>,
+    compile_options: ShaderCompileOptions,
+    variants: HashMap<u64, Variant<P>>,
+}
+
+impl<P: Pod> ShaderVariantSet<P> {
+    pub fn new(
+        device: &Device,
+        info: ShaderVariantSetInfo,
+    ) -> std::result::Result<Self, ShaderVariantError> {
+        let ShaderVariantSetInfo {
+            name,
+            sources: RasterPipelineSources { stages, template },
+            options,
+            compile_options,
+        } = info;
+        let compiler =
+            ShaderCompiler::new().map_err(|_| ShaderVariantError::CompilerUnavailable)?;
+        let mut set = Self {
+            device: device.clone(),
+            compiler,
+            name,
+            stages,
+            sources: Vec::new(),
+            source_hash: 0,
+            template,
+            options,
+            compile_options,
+            variants: HashMap::new(),
+        };
+        set.refresh_sources()?;
+        Ok(set)
+    }
+
+    //rereads the source files, variants of the old sources stay cached until they are evicted
+    //variants whose includes changed are dropped, the options alone would map to them again
+    pub fn refresh_sources(&mut self) -> std::result::Result<(), ShaderVariantError> {
+        let mut hasher = hash_map::DefaultHasher::new();
+        let mut sources = Vec::with_capacity(self.stages.len());
+        for (stage, path) in &self.stages {
+            let text = fs::read_to_string(path).map_err(|error| ShaderVariantError::Compile {
+                path: path.clone(),
+                error: ShaderCompileError::Io {
+                    path: path.clone(),
+                    error,
+                },
+            })?;
+            path.hash(&mut hasher);
+            text.hash(&mut hasher);
+            sources.push(StageSource {
+                stage: *stage,
+                path: path.clone(),
+                text,
+            });
+        }
+        self.sources = sources;
+        self.source_hash = hasher.finish();
+        self.variants.retain(|_, variant| {
+            variant
+                .includes
+                .iter()
+                .all(|(path, hash)| hash.is_some() && content_hash(path) == *hash)
+        });
+        Ok(())
+    }
+
+    //options missing from the defines take their first value
+    pub fn get(
+        &mut self,
+        defines: &[(&str, &str)],
+    ) -> std::result::Result<RasterPipeline<P>, ShaderVariantError> {
+        let defines = self.resolve(defines)?;
+        let key = self.key(&defines);
+        if let Some(variant) = self.variants.get_mut(&key) {
+            variant.used = true;
+            return Ok(variant.pipeline.clone());
+        }
+
+        let (pipeline, includes) = self.compile(defines)?;
+        self.variants.insert(
+            key,
+            Variant {
+                pipeline: pipeline.clone(),
+                used: true,
+                includes,
+            },
+        );
+        Ok(pipeline)
+    }
+
+    //compiled variants currently cached
+    pub fn len(&self) -> usize {
+        self.variants.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty()
+    }
+
+    //every allowed combination of option values, compiled or not
+    pub fn permutation_count(&self) -> usize {
+        self.options
+            .iter()
+            .map(|option| option.values.len())
+            .product()
+    }
+
+    //drops the variants nobody requested since the last call, returns how many were dropped
+    pub fn evict_unused(&mut self) -> usize {
+        let before = self.variants.len();
+        self.variants.retain(|_, variant| variant.used);
+        for variant in self.variants.values_mut() {
+            variant.used = false;
+        }
+        before - self.variants.len()
+    }
+
+    //in the order of the options, so equal variants always hash the same
+    fn resolve(
+        &self,
+        defines: &[(&str, &str)],
+    ) -> std::result::Result<Vec<(String, String)>, ShaderVariantError> {
+        if let Some((name, _)) = defines
+            .iter()
+            .find(|(name, _)| self.options.iter().all(|option| option.name != *name))
+        {
+            return Err(ShaderVariantError::UnknownOption(name.to_string()));
+        }
+        self.options
+            .iter()
+            .map(|option| {
+                let value = defines
+                    .iter()
+                    .rev()
+                    .find(|(name, _)| *name == option.name)
+                    .map_or(option.values[0].as_str(), |(_, value)| *value);
+                if option.values.iter().all(|allowed| allowed != value) {
+                    return Err(ShaderVariantError::InvalidValue {
+                        option: option.name.clone(),
+                        value: value.to_string(),
+                    });
+                }
+                Ok((option.name.clone(), value.to_string()))
+            })
+            .collect()
+    }
+
+    fn key(&self, defines: &[(String, String)]) -> u64 {
+        let mut hasher = hash_map::DefaultHasher::new();
+        self.source_hash.hash(&mut hasher);
+        defines.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn compile(
+        &self,
+        defines: Vec<(String, String)>,
+    ) -> std::result::Result<(RasterPipeline<P>, Includes), ShaderVariantError> {
+        let variant_name = defines
+            .iter()
+            .fold(self.name.clone(), |name, (option, value)| {
+                format!("{} {}={}", name, option, value)
+            });
+        let mut options = self.compile_options.clone();
+        options.defines.extend(defines);
+
+        let shaders = self
+            .sources
+            .iter()
+            .map(|source| {
+                self.compiler
+                    .compile_source(
+                        &source.text,
+                        &source.path,
+                        source.stage.execution_model(),
+                        &options,
+                    )
+                    .map_err(|error| ShaderVariantError::Compile {
+                        path: source.path.clone(),
+                        error,
+                    })
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let info = self.sources.iter().zip(&shaders).fold(
+            RasterPipelineInfo {
+                name: variant_name,
+                ..self.template.clone()
+            },
+            |info, (source, shader)| info.with_shader(source.stage, shader.shader_info()),
+        );
+        let pipeline = self
+            .device
+            .create_raster_pipeline::<P>(&info)
+            .map_err(ShaderVariantError::Create)?;
+
+        let mut includes = shaders
+            .iter()
+            .flat_map(|shader| shader.includes())
+            .cloned()
+            .collect::<Vec<_>>();
+        includes.sort();
+        includes.dedup();
+        let includes = includes
+            .into_iter()
+            .map(|path| {
+                let hash = content_hash(&path);
+                (path, hash)
+            })
+            .collect();
+        Ok((pipeline, includes))
+    }
+}
@@ -0,0 +1,1055 @@
+use raw_window_handle::{
+    HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
+};
+use std::cell;
+use std::ffi;
+use std::mem;
+use std::os;
+use std::panic;
+use std::ptr;
+use std::sync;
+use std::time;
+
+use crate::command_recorder::{
+    CommandRecorderInfo, ImageBufferCopyInfo, ImageMemoryBarrierInfo, MemoryBarrierInfo,
+};
+use crate::device::{Device, PresentError, PresentStatus, SubmitError};
+use crate::loader;
+use crate::sync::{
+    BinarySemaphore, BinarySemaphoreInfo, TimelineSemaphore, TimelineSemaphoreInfo, WaitError,
+};
+use crate::types::*;
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PresentMode {
+    Immediate = daxa_sys::VkPresentModeKHR_VK_PRESENT_MODE_IMMEDIATE_KHR,
+    Mailbox = daxa_sys::VkPresentModeKHR_VK_PRESENT_MODE_MAILBOX_KHR,
+    #[default]
+    Fifo = daxa_sys::VkPresentModeKHR_VK_PRESENT_MODE_FIFO_KHR,
+    FifoRelaxed = daxa_sys::VkPresentModeKHR_VK_PRESENT_MODE_FIFO_RELAXED_KHR,
+}
+
+//scores a surface format, higher is better, None rejects the format outright
+pub type SurfaceFormatSelector = dyn FnMut(Format) -> std::option::Option<i32> + Send;
+
+//same preference order as daxa's own default, 8 bit unorm first so shaders do the srgb encode
+pub fn default_surface_format_selector(format: Format) -> std::option::Option<i32> {
+    Some(match format {
+        Format::B8G8R8A8_UNORM => 90,
+        Format::R8G8B8A8_UNORM => 80,
+        Format::B8G8R8A8_SRGB => 70,
+        Format::R8G8B8A8_SRGB => 60,
+        _ => 0,
+    })
+}
+
+thread_local! {
+    //like the device selector, daxa calls this synchronously from swapchain creation and resize
+    static ACTIVE_FORMAT_SELECTOR: cell::RefCell<std::option::Option<sync::Arc<sync::Mutex<SurfaceFormatSelector>>>> =
+        cell::RefCell::new(None);
+    //daxa still picks a format when every one was rejected, so whether any was accepted is tracked here
+    static FORMAT_ACCEPTED: cell::Cell<bool> = cell::Cell::new(false);
+}
+
+extern "C" fn format_selector_trampoline(format: daxa_sys::VkFormat) -> i32 {
+    const REJECTED: i32 = i32::MIN;
+    ACTIVE_FORMAT_SELECTOR.with(|active| {
+        let Some(selector) = active.borrow().clone() else {
+            return REJECTED;
+        };
+        let Ok(format) = Format::try_from(format) else {
+            return REJECTED;
+        };
+        //unwinding into C is undefined, so a panicking selector just rejects the format
+        panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let mut selector = selector.lock().unwrap_or_else(|e| e.into_inner());
+            (&mut *selector)(format)
+        }))
+        .ok()
+        .flatten()
+        .map_or(REJECTED, |score| {
+            FORMAT_ACCEPTED.with(|accepted| accepted.set(true));
+            //every accepted format has to outrank the rejected ones
+            score.max(REJECTED + 1)
+        })
+    })
+}
+
+pub(crate) struct FormatSelectorGuard {
+    previous: std::option::Option<sync::Arc<sync::Mutex<SurfaceFormatSelector>>>,
+    previous_accepted: bool,
+}
+
+impl FormatSelectorGuard {
+    //whether the selector accepted any format daxa offered while the guard was alive
+    pub(crate) fn accepted_any(&self) -> bool {
+        FORMAT_ACCEPTED.with(cell::Cell::get)
+    }
+}
+
+impl Drop for FormatSelectorGuard {
+    fn drop(&mut self) {
+        ACTIVE_FORMAT_SELECTOR.with(|active| *active.borrow_mut() = self.previous.take());
+        FORMAT_ACCEPTED.with(|accepted| accepted.set(self.previous_accepted));
+    }
+}
+
+#[derive(Clone)]
+pub struct SwapchainInfo {
+    surface_format_selector: sync::Arc<sync::Mutex<SurfaceFormatSelector>>,
+    present_mode: PresentMode,
+    image_usage: ImageUsageFlags,
+    max_allowed_frames_in_flight: usize,
+    name: String,
+}
+
+impl Default for SwapchainInfo {
+    fn default() -> Self {
+        Self {
+            surface_format_selector: sync::Arc::new(sync::Mutex::new(
+                default_surface_format_selector,
+            )),
+            present_mode: PresentMode::Fifo,
+            image_usage: ImageUsageFlags::TRANSFER_DST,
+            max_allowed_frames_in_flight: 2,
+            name: String::new(),
+        }
+    }
+}
+
+impl SwapchainInfo {
+    pub fn builder() -> SwapchainInfoBuilder {
+        SwapchainInfoBuilder::default()
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    pub fn image_usage(&self) -> ImageUsageFlags {
+        self.image_usage
+    }
+
+    pub fn max_allowed_frames_in_flight(&self) -> usize {
+        self.max_allowed_frames_in_flight
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    //the best scoring format the selector accepts, the same choice daxa makes through the trampoline
+    pub(crate) fn select_format(
+        &self,
+        formats: impl IntoIterator<Item = Format>,
+    ) -> std::option::Option<Format> {
+        let mut selector = self
+            .surface_format_selector
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        formats
+            .into_iter()
+            .filter_map(|format| Some((format, (&mut *selector)(format)?)))
+            .max_by_key(|&(_, score)| score)
+            .map(|(format, _)| format)
+    }
+
+    //makes this info's selector the one the trampoline forwards to until the guard drops
+    pub(crate) fn bind_format_selector(&self) -> FormatSelectorGuard {
+        let previous = ACTIVE_FORMAT_SELECTOR
+            .with(|active| active.replace(Some(self.surface_format_selector.clone())));
+        let previous_accepted = FORMAT_ACCEPTED.with(|accepted| accepted.replace(false));
+        FormatSelectorGuard {
+            previous,
+            previous_accepted,
+        }
+    }
+
+    pub(crate) fn as_c_info(
+        &self,
+        window: NativeWindow,
+    ) -> std::result::Result<CInfo<daxa_sys::daxa_SwapchainInfo>, InvalidName> {
+        CInfo::new(&self.name, |name| daxa_sys::daxa_SwapchainInfo {
+            native_window: window.handle,
+            native_window_platform: window.platform,
+            surface_format_selector: Some(format_selector_trampoline),
+            present_mode: self.present_mode as _,
+            present_operation:
+                daxa_sys::VkSurfaceTransformFlagBitsKHR_VK_SURFACE_TRANSFORM_IDENTITY_BIT_KHR,
+            image_usage: self.image_usage.bits(),
+            max_allowed_frames_in_flight: self.max_allowed_frames_in_flight as _,
+            name,
+        })
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct SwapchainInfoBuilder {
+    info: SwapchainInfo,
+}
+
+impl SwapchainInfoBuilder {
+    pub fn surface_format_selector(
+        mut self,
+        selector: impl FnMut(Format) -> std::option::Option<i32> + Send + 'static,
+    ) -> Self {
+        self.info.surface_format_selector = sync::Arc::new(sync::Mutex::new(selector));
+        self
+    }
+
+    pub fn present_mode(mut self, present_mode: PresentMode) -> Self {
+        self.info.present_mode = present_mode;
+        self
+    }
+
+    //daxa always adds color attachment usage on top of these
+    pub fn image_usage(mut self, image_usage: ImageUsageFlags) -> Self {
+        self.info.image_usage = image_usage;
+        self
+    }
+
+    pub fn max_allowed_frames_in_flight(mut self, count: usize) -> Self {
+        self.info.max_allowed_frames_in_flight = count;
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.info.name = name.into();
+        self
+    }
+
+    pub fn build(self) -> SwapchainInfo {
+        self.info
+    }
+}
+
+#[derive(Debug)]
+pub enum SwapchainCreateError {
+    //only x11, wayland and win32 surfaces can be created by daxa
+    UnsupportedWindow,
+    //the surface format selector rejected every format on offer
+    NoSuitableFormat,
+    Daxa(DaxaError),
+}
+
+impl std::fmt::Display for SwapchainCreateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for SwapchainCreateError {}
+
+#[derive(Debug)]
+pub enum AcquireError {
+    //the swapchain no longer matches its surface and has to be resized before acquiring again
+    OutOfDate,
+    DeviceLost,
+    Daxa(DaxaError),
+}
+
+impl std::fmt::Display for AcquireError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for AcquireError {}
+
+#[derive(Debug)]
+pub enum ResizeError {
+    //the surface format selector rejected every format on offer, daxa kept a format of its own choosing
+    NoSuitableFormat,
+    Daxa(DaxaError),
+}
+
+impl std::fmt::Display for ResizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for ResizeError {}
+
+//bindgen only sees vulkan_core.h, so the platform surface infos are spelled out here
+#[repr(C)]
+struct VkXlibSurfaceCreateInfoKHR {
+    s_type: daxa_sys::VkStructureType,
+    p_next: *const ffi::c_void,
+    flags: daxa_sys::VkFlags,
+    dpy: *mut ffi::c_void,
+    window: os::raw::c_ulong,
+}
+
+#[repr(C)]
+struct VkWaylandSurfaceCreateInfoKHR {
+    s_type: daxa_sys::VkStructureType,
+    p_next: *const ffi::c_void,
+    flags: daxa_sys::VkFlags,
+    display: *mut ffi::c_void,
+    surface: *mut ffi::c_void,
+}
+
+#[repr(C)]
+struct VkWin32SurfaceCreateInfoKHR {
+    s_type: daxa_sys::VkStructureType,
+    p_next: *const ffi::c_void,
+    flags: daxa_sys::VkFlags,
+    hinstance: *mut ffi::c_void,
+    hwnd: *mut ffi::c_void,
+}
+
+//every platform's vkCreate*SurfaceKHR has this shape, only the create info differs
+type CreateSurface = unsafe extern "system" fn(
+    daxa_sys::VkInstance,
+    *const ffi::c_void,
+    *const daxa_sys::VkAllocationCallbacks,
+    *mut daxa_sys::VkSurfaceKHR,
+) -> daxa_sys::VkResult;
+
+const XLIB_SURFACE_CREATE_INFO: daxa_sys::VkStructureType = 1000004000;
+const WAYLAND_SURFACE_CREATE_INFO: daxa_sys::VkStructureType = 1000006000;
+const WIN32_SURFACE_CREATE_INFO: daxa_sys::VkStructureType = 1000009000;
+
+//the formats the window's surface offers, queried through a surface of our own before daxa creates one
+//None when no such surface can be made up front, xcb windows have no xlib display to create it with
+pub(crate) unsafe fn surface_formats(
+    window: &(impl HasRawWindowHandle + HasRawDisplayHandle),
+    instance: daxa_sys::VkInstance,
+    physical_device: daxa_sys::VkPhysicalDevice,
+) -> std::result::Result<std::option::Option<Vec<Format>>, DaxaError> {
+    let xlib;
+    let wayland;
+    let win32;
+    let (create_info, create_surface): (*const ffi::c_void, &[u8]) =
+        match (window.raw_window_handle(), window.raw_display_handle()) {
+            (RawWindowHandle::Xlib(window), RawDisplayHandle::Xlib(display))
+                if !display.display.is_null() =>
+            {
+                xlib = VkXlibSurfaceCreateInfoKHR {
+                    s_type: XLIB_SURFACE_CREATE_INFO,
+                    p_next: ptr::null(),
+                    flags: 0,
+                    dpy: display.display,
+                    window: window.window,
+                };
+                (ptr::addr_of!(xlib).cast(), b"vkCreateXlibSurfaceKHR\0")
+            }
+            (RawWindowHandle::Wayland(window), RawDisplayHandle::Wayland(display)) => {
+                wayland = VkWaylandSurfaceCreateInfoKHR {
+                    s_type: WAYLAND_SURFACE_CREATE_INFO,
+                    p_next: ptr::null(),
+                    flags: 0,
+                    display: display.display,
+                    surface: window.surface,
+                };
+                (
+                    ptr::addr_of!(wayland).cast(),
+                    b"vkCreateWaylandSurfaceKHR\0",
+                )
+            }
+            (RawWindowHandle::Win32(window), RawDisplayHandle::Windows(_)) => {
+                win32 = VkWin32SurfaceCreateInfoKHR {
+                    s_type: WIN32_SURFACE_CREATE_INFO,
+                    p_next: ptr::null(),
+                    flags: 0,
+                    hinstance: window.hinstance,
+                    hwnd: window.hwnd,
+                };
+                (ptr::addr_of!(win32).cast(), b"vkCreateWin32SurfaceKHR\0")
+            }
+            _ => return Ok(None),
+        };
+
+    //daxa only enables the surface extensions of the platforms it was built for
+    let load = |name: &[u8]| loader::get_instance_proc_addr(instance, name.as_ptr().cast());
+    let Some(create_surface) =
+        mem::transmute::<_, std::option::Option<CreateSurface>>(load(create_surface))
+    else {
+        return Ok(None);
+    };
+    let (Some(get_formats), Some(destroy_surface)) = (
+        mem::transmute::<_, daxa_sys::PFN_vkGetPhysicalDeviceSurfaceFormatsKHR>(load(
+            b"vkGetPhysicalDeviceSurfaceFormatsKHR\0",
+        )),
+        mem::transmute::<_, daxa_sys::PFN_vkDestroySurfaceKHR>(load(b"vkDestroySurfaceKHR\0")),
+    ) else {
+        return Ok(None);
+    };
+
+    let check = |result: daxa_sys::VkResult, operation: &'static str| {
+        //positive codes such as VK_INCOMPLETE are not failures
+        if result < 0 {
+            Err(DaxaError::new(result, operation, ""))
+        } else {
+            Ok(())
+        }
+    };
+
+    let mut surface = mem::zeroed();
+    check(
+        create_surface(instance, create_info, ptr::null(), &mut surface),
+        "create_surface",
+    )?;
+    let formats: std::result::Result<Vec<_>, DaxaError> = (|| {
+        let mut count = 0;
+        check(
+            get_formats(physical_device, surface, &mut count, ptr::null_mut()),
+            "vkGetPhysicalDeviceSurfaceFormatsKHR",
+        )?;
+        let mut formats = vec![mem::zeroed::<daxa_sys::VkSurfaceFormatKHR>(); count as usize];
+        check(
+            get_formats(physical_device, surface, &mut count, formats.as_mut_ptr()),
+            "vkGetPhysicalDeviceSurfaceFormatsKHR",
+        )?;
+        formats.truncate(count as usize);
+        Ok(formats)
+    })();
+    destroy_surface(instance, surface, ptr::null());
+
+    //formats the wrapper has no variant for are ones the trampoline rejects as well
+    Ok(Some(
+        formats?
+            .into_iter()
+            .filter_map(|format| Format::try_from(format.format).ok())
+            .collect(),
+    ))
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct NativeWindow {
+    handle: daxa_sys::daxa_NativeWindowHandle,
+    platform: daxa_sys::daxa_NativeWindowPlatform,
+}
+
+impl NativeWindow {
+    pub(crate) fn new(
+        window: &(impl HasRawWindowHandle + HasRawDisplayHandle),
+    ) -> std::result::Result<Self, SwapchainCreateError> {
+        let (handle, platform) = match (window.raw_window_handle(), window.raw_display_handle()) {
+            (RawWindowHandle::Xlib(window), RawDisplayHandle::Xlib(_)) => (
+                window.window as usize as _,
+                daxa_sys::daxa_NativeWindowPlatform_DAXA_NATIVE_WINDOW_PLATFORM_XLIB_API,
+            ),
+            //xcb window ids are plain x11 window ids, so they go through the xlib surface just fine
+            (RawWindowHandle::Xcb(window), RawDisplayHandle::Xcb(_)) => (
+                window.window as usize as _,
+                daxa_sys::daxa_NativeWindowPlatform_DAXA_NATIVE_WINDOW_PLATFORM_XLIB_API,
+            ),
+            (RawWindowHandle::Wayland(window), RawDisplayHandle::Wayland(_)) => (
+                window.surface as _,
+                daxa_sys::daxa_NativeWindowPlatform_DAXA_NATIVE_WINDOW_PLATFORM_WAYLAND_API,
+            ),
+            (RawWindowHandle::Win32(window), RawDisplayHandle::Windows(_)) => (
+                window.hwnd as _,
+                daxa_sys::daxa_NativeWindowPlatform_DAXA_NATIVE_WINDOW_PLATFORM_WIN32_API,
+            ),
+            _ => return Err(SwapchainCreateError::UnsupportedWindow),
+        };
+        Ok(Self { handle, platform })
+    }
+}
+
+struct SwapchainHandle {
+    handle: daxa_sys::daxa_Swapchain,
+    info: SwapchainInfo,
+    device: Device,
+}
+
+impl Drop for SwapchainHandle {
+    fn drop(&mut self) {
+        unsafe {
+            daxa_sys::daxa_swapchain_dec_refcnt(self.handle);
+        }
+    }
+}
+
+impl SwapchainHandle {
+    //the swapchain keeps its own reference, the wrapper takes another it releases on drop
+    unsafe fn share_binary_semaphore(
+        &self,
+        semaphore: daxa_sys::daxa_BinarySemaphore,
+        role: &str,
+    ) -> BinarySemaphore {
+        daxa_sys::daxa_binary_semaphore_inc_refcnt(semaphore);
+        BinarySemaphore::from_raw(
+            self.device.clone(),
+            semaphore,
+            BinarySemaphoreInfo {
+                name: format!("{} {}", self.info.name(), role),
+            },
+        )
+    }
+}
+
+//a finished headless frame, rows are tightly packed with 4 bytes per texel
+pub struct HeadlessFrame<'a> {
+    //the cpu timeline value of the frame, starting at 1
+    pub index: u64,
+    pub extent: Extent2D,
+    pub format: Format,
+    pub data: &'a [u8],
+}
+
+pub type HeadlessPresentCallback = dyn FnMut(&HeadlessFrame) + Send;
+
+//formats a surface commonly offers, headless swapchains let the selector pick among these
+const HEADLESS_FORMATS: [Format; 4] = [
+    Format::B8G8R8A8_UNORM,
+    Format::R8G8B8A8_UNORM,
+    Format::B8G8R8A8_SRGB,
+    Format::R8G8B8A8_SRGB,
+];
+const HEADLESS_TEXEL_SIZE: usize = 4;
+
+struct HeadlessSlot {
+    image: Image,
+    acquire_semaphore: BinarySemaphore,
+    present_semaphore: BinarySemaphore,
+}
+
+//each slot copies into its own buffer, the copy is only waited on once the slot is acquired again
+struct HeadlessReadback {
+    buffers: Vec<Buffer>,
+    //signalled with the index of the frame whose copy finished
+    timeline: TimelineSemaphore,
+    //the frame each slot's buffer is being copied from
+    pending: Vec<std::option::Option<u64>>,
+}
+
+//a frame copied out of its readback buffer, handed to the callback once no lock is held
+struct FinishedFrame {
+    index: u64,
+    data: Vec<u8>,
+}
+
+#[derive(Default)]
+struct HeadlessState {
+    cpu_timeline: u64,
+    acquired: std::option::Option<usize>,
+}
+
+//renders into a ring of plain images instead of a surface, for machines without a window system
+pub(crate) struct HeadlessSwapchain {
+    device: Device,
+    info: SwapchainInfo,
+    extent: Extent2D,
+    format: Format,
+    slots: Vec<HeadlessSlot>,
+    gpu_timeline: TimelineSemaphore,
+    readback: sync::Mutex<std::option::Option<HeadlessReadback>>,
+    //apart from the other state, so the callback can use the swapchain
+    callback: std::option::Option<sync::Mutex<Box<HeadlessPresentCallback>>>,
+    state: sync::Mutex<HeadlessState>,
+}
+
+impl HeadlessSwapchain {
+    pub(crate) fn new(
+        device: Device,
+        extent: Extent2D,
+        info: SwapchainInfo,
+        on_present: std::option::Option<Box<HeadlessPresentCallback>>,
+    ) -> std::result::Result<Self, SwapchainCreateError> {
+        let format = info
+            .select_format(HEADLESS_FORMATS)
+            .ok_or(SwapchainCreateError::NoSuitableFormat)?;
+
+        let frames = info.max_allowed_frames_in_flight.max(1);
+        let mut slots = Vec::with_capacity(frames);
+        for index in 0..frames {
+            let image = device
+                .create_image(&ImageInfo {
+                    extent: Extent::TwoDim(extent.width, extent.height),
+                    format,
+                    usage: info.image_usage
+                        | ImageUsageFlags::COLOR_ATTACHMENT
+                        | ImageUsageFlags::TRANSFER_SRC,
+                    name: format!("{} image {}", info.name, index),
+                    ..Default::default()
+                })
+                .map_err(SwapchainCreateError::Daxa)?;
+            let acquire_semaphore = device
+                .create_binary_semaphore(&BinarySemaphoreInfo {
+                    name: format!("{} acquire semaphore {}", info.name, index),
+                })
+                .map_err(SwapchainCreateError::Daxa)?;
+            let present_semaphore = device
+                .create_binary_semaphore(&BinarySemaphoreInfo {
+                    name: format!("{} present semaphore {}", info.name, index),
+                })
+                .map_err(SwapchainCreateError::Daxa)?;
+            slots.push(HeadlessSlot {
+                image,
+                acquire_semaphore,
+                present_semaphore,
+            });
+        }
+
+        let gpu_timeline = device
+            .create_timeline_semaphore(&TimelineSemaphoreInfo {
+                initial_value: 0,
+                name: format!("{} gpu timeline", info.name),
+            })
+            .map_err(SwapchainCreateError::Daxa)?;
+
+        let readback = match on_present {
+            Some(_) => Some(HeadlessReadback {
+                buffers: (0..frames)
+                    .map(|index| {
+                        device.create_buffer(&BufferInfo {
+                            size: extent.width as usize
+                                * extent.height as usize
+                                * HEADLESS_TEXEL_SIZE,
+                            allocate_info: MemoryFlags::HOST_ACCESS_RANDOM,
+                            name: format!("{} readback {}", info.name, index),
+                        })
+                    })
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(SwapchainCreateError::Daxa)?,
+                timeline: device
+                    .create_timeline_semaphore(&TimelineSemaphoreInfo {
+                        initial_value: 0,
+                        name: format!("{} readback timeline", info.name),
+                    })
+                    .map_err(SwapchainCreateError::Daxa)?,
+                pending: vec![None; frames],
+            }),
+            None => None,
+        };
+
+        Ok(Self {
+            device,
+            info,
+            extent,
+            format,
+            slots,
+            gpu_timeline,
+            readback: sync::Mutex::new(readback),
+            callback: on_present.map(sync::Mutex::new),
+            state: sync::Mutex::new(HeadlessState::default()),
+        })
+    }
+
+    fn state(&self) -> sync::MutexGuard<'_, HeadlessState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn current_slot(&self) -> &HeadlessSlot {
+        let cpu_timeline = self.state().cpu_timeline;
+        &self.slots[(cpu_timeline % self.slots.len() as u64) as usize]
+    }
+
+    fn readback(&self) -> sync::MutexGuard<'_, std::option::Option<HeadlessReadback>> {
+        self.readback.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    //copies out every frame whose readback finished, in order, after waiting for frame wait_for
+    fn take_finished(
+        &self,
+        readback: &mut HeadlessReadback,
+        wait_for: u64,
+    ) -> std::result::Result<Vec<FinishedFrame>, WaitError> {
+        readback
+            .timeline
+            .wait_for_value(wait_for, time::Duration::MAX)?;
+        let finished = readback.timeline.value().map_err(WaitError::Daxa)?;
+        let mut slots = (0..readback.pending.len())
+            .filter(|&slot| readback.pending[slot].map_or(false, |index| index <= finished))
+            .collect::<Vec<_>>();
+        slots.sort_by_key(|&slot| readback.pending[slot]);
+
+        let mut frames = Vec::with_capacity(slots.len());
+        for slot in slots {
+            let data = match readback.buffers[slot].map::<u8>() {
+                Ok(data) => data.to_vec(),
+                Err(MapError::Daxa(error)) => return Err(WaitError::Daxa(error)),
+                Err(error) => unreachable!("headless readback buffer failed to map: {}", error),
+            };
+            frames.push(FinishedFrame {
+                index: readback.pending[slot].take().unwrap(),
+                data,
+            });
+        }
+        Ok(frames)
+    }
+
+    //called without holding the state or readback lock
+    fn hand_over(&self, frames: Vec<FinishedFrame>) {
+        let Some(callback) = &self.callback else {
+            return;
+        };
+        let mut callback = callback.lock().unwrap_or_else(|e| e.into_inner());
+        for frame in frames {
+            (&mut *callback)(&HeadlessFrame {
+                index: frame.index,
+                extent: self.extent,
+                format: self.format,
+                data: &frame.data,
+            });
+        }
+    }
+
+    fn acquire_next_image(&self) -> std::result::Result<ImageId, AcquireError> {
+        let (image, finished) = self.acquire_slot()?;
+        self.hand_over(finished);
+        Ok(image)
+    }
+
+    fn acquire_slot(&self) -> std::result::Result<(ImageId, Vec<FinishedFrame>), AcquireError> {
+        let mut state = self.state();
+        let frames = self.slots.len() as u64;
+        let cpu_timeline = state.cpu_timeline + 1;
+        let wait_error = |error| match error {
+            WaitError::DeviceLost => AcquireError::DeviceLost,
+            WaitError::Daxa(error) => AcquireError::Daxa(error),
+            WaitError::Timeout => unreachable!("waits without a timeout cannot time out"),
+            WaitError::NoSemaphores | WaitError::MixedDevices => {
+                unreachable!("waits on a single semaphore cannot fail like this")
+            }
+        };
+        //a slot is reused once the gpu finished the frame that used it last, like daxa's frames in flight
+        self.gpu_timeline
+            .wait_for_value(cpu_timeline.saturating_sub(frames), time::Duration::MAX)
+            .map_err(wait_error)?;
+
+        let slot = (cpu_timeline % frames) as usize;
+        //the slot's buffer is copied into again when this frame is presented
+        let finished = match self.readback().as_mut() {
+            Some(readback) => {
+                let wait_for = readback.pending[slot].unwrap_or(0);
+                self.take_finished(readback, wait_for).map_err(wait_error)?
+            }
+            None => Vec::new(),
+        };
+        //no presentation engine signals the acquire semaphore, an empty submit stands in for it
+        self.device
+            .submit(&CommandSubmitInfo {
+                signal_binary_semaphores: std::slice::from_ref(&self.slots[slot].acquire_semaphore),
+                ..Default::default()
+            })
+            .map_err(|error| match error {
+                SubmitError::DeviceLost => AcquireError::DeviceLost,
+                SubmitError::Daxa(error) => AcquireError::Daxa(error),
+            })?;
+
+        state.cpu_timeline = cpu_timeline;
+        state.acquired = Some(slot);
+        Ok((self.slots[slot].image.id(), finished))
+    }
+
+    //like a surface swapchain the image is expected in PresentSrc once the waited semaphores signal
+    pub(crate) fn present(
+        &self,
+        wait_binary_semaphores: &[BinarySemaphore],
+    ) -> std::result::Result<PresentStatus, PresentError> {
+        let finished = self.present_slot(wait_binary_semaphores)?;
+        self.hand_over(finished);
+        Ok(PresentStatus::Optimal)
+    }
+
+    //returns the frames whose readback already finished, without waiting for this one
+    fn present_slot(
+        &self,
+        wait_binary_semaphores: &[BinarySemaphore],
+    ) -> std::result::Result<Vec<FinishedFrame>, PresentError> {
+        let (slot, cpu_timeline) = {
+            let mut state = self.state();
+            let slot = state.acquired.take().ok_or(PresentError::NotAcquired)?;
+            (slot, state.cpu_timeline)
+        };
+        let submit_error = |error| match error {
+            SubmitError::DeviceLost => PresentError::DeviceLost,
+            SubmitError::Daxa(error) => PresentError::Daxa(error),
+        };
+
+        let mut readback = self.readback();
+        let Some(readback) = readback.as_mut() else {
+            //the semaphores still have to be waited on, or they could never be signalled again
+            self.device
+                .submit(&CommandSubmitInfo {
+                    wait_binary_semaphores,
+                    ..Default::default()
+                })
+                .map_err(submit_error)?;
+            return Ok(Vec::new());
+        };
+
+        let image = self.slots[slot].image.id();
+        let mut recorder = self
+            .device
+            .create_command_recorder(&CommandRecorderInfo {
+                name: format!("{} readback", self.info.name()),
+            })
+            .map_err(PresentError::Daxa)?;
+        recorder
+            .pipeline_barriers(
+                &[],
+                &[ImageMemoryBarrierInfo {
+                    src_access: Access::new(
+                        PipelineStageFlags::ALL_COMMANDS_BIT,
+                        AccessTypeFlags::READ_WRITE,
+                    ),
+                    dst_access: Access::new(
+                        PipelineStageFlags::TRANSFER_BIT,
+                        AccessTypeFlags::READ,
+                    ),
+                    src_layout: ImageLayout::PresentSrc,
+                    dst_layout: ImageLayout::TransferSrcOptimal,
+                    image_slice: ImageMipArraySlice::default(),
+                    image,
+                }],
+            )
+            .map_err(PresentError::Daxa)?;
+        recorder
+            .copy_image_to_buffer(&ImageBufferCopyInfo {
+                image,
+                image_layout: ImageLayout::TransferSrcOptimal,
+                image_slice: ImageArraySlice::default(),
+                image_offset: Offset3D::default(),
+                image_extent: Extent3D {
+                    width: self.extent.width,
+                    height: self.extent.height,
+                    depth: 1,
+                },
+                buffer: readback.buffers[slot].id(),
+                buffer_offset: 0,
+            })
+            .map_err(PresentError::Daxa)?;
+        recorder
+            .pipeline_barriers(
+                &[MemoryBarrierInfo {
+                    src_access: Access::new(
+                        PipelineStageFlags::TRANSFER_BIT,
+                        AccessTypeFlags::WRITE,
+                    ),
+                    dst_access: Access::new(PipelineStageFlags::HOST_BIT, AccessTypeFlags::READ),
+                }],
+                &[ImageMemoryBarrierInfo {
+                    src_access: Access::new(
+                        PipelineStageFlags::TRANSFER_BIT,
+                        AccessTypeFlags::READ,
+                    ),
+                    dst_access: Access::new(
+                        PipelineStageFlags::ALL_COMMANDS_BIT,
+                        AccessTypeFlags::READ_WRITE,
+                    ),
+                    src_layout: ImageLayout::TransferSrcOptimal,
+                    dst_layout: ImageLayout::PresentSrc,
+                    image_slice: ImageMipArraySlice::default(),
+                    image,
+                }],
+            )
+            .map_err(PresentError::Daxa)?;
+        let commands = recorder.complete().map_err(PresentError::Daxa)?;
+
+        self.device
+            .submit(&CommandSubmitInfo {
+                wait_stages: PipelineStageFlags::TRANSFER_BIT,
+                command_lists: &[commands],
+                wait_binary_semaphores,
+                signal_timeline_semaphores: &[(readback.timeline.clone(), cpu_timeline)],
+                ..Default::default()
+            })
+            .map_err(submit_error)?;
+        readback.pending[slot] = Some(cpu_timeline);
+        self.take_finished(readback, 0)
+            .map_err(|error| match error {
+                WaitError::DeviceLost => PresentError::DeviceLost,
+                WaitError::Daxa(error) => PresentError::Daxa(error),
+                WaitError::Timeout | WaitError::NoSemaphores | WaitError::MixedDevices => {
+                    unreachable!("a zero wait on a single semaphore cannot fail like this")
+                }
+            })
+    }
+}
+
+//frames still in flight reach the callback before the swapchain goes away
+impl Drop for HeadlessSwapchain {
+    fn drop(&mut self) {
+        let finished = {
+            let mut readback = self.readback();
+            let Some(readback) = readback.as_mut() else {
+                return;
+            };
+            let last = readback.pending.iter().flatten().max().copied();
+            //a lost device has nothing left to deliver
+            self.take_finished(readback, last.unwrap_or(0))
+                .unwrap_or_default()
+        };
+        self.hand_over(finished);
+    }
+}
+
+enum SwapchainInner {
+    Surface(SwapchainHandle),
+    Headless(HeadlessSwapchain),
+}
+
+#[derive(Clone)]
+pub struct Swapchain {
+    inner: sync::Arc<SwapchainInner>,
+}
+
+impl Swapchain {
+    pub(crate) fn from_raw(
+        device: Device,
+        handle: daxa_sys::daxa_Swapchain,
+        info: SwapchainInfo,
+    ) -> Self {
+        Self {
+            inner: sync::Arc::new(SwapchainInner::Surface(SwapchainHandle {
+                handle,
+                info,
+                device,
+            })),
+        }
+    }
+
+    pub(crate) fn headless(headless: HeadlessSwapchain) -> Self {
+        Self {
+            inner: sync::Arc::new(SwapchainInner::Headless(headless)),
+        }
+    }
+
+    //None for headless swapchains, which daxa knows nothing about
+    pub(crate) fn handle(&self) -> std::option::Option<daxa_sys::daxa_Swapchain> {
+        match &*self.inner {
+            SwapchainInner::Surface(surface) => Some(surface.handle),
+            SwapchainInner::Headless(_) => None,
+        }
+    }
+
+    pub(crate) fn as_headless(&self) -> std::option::Option<&HeadlessSwapchain> {
+        match &*self.inner {
+            SwapchainInner::Surface(_) => None,
+            SwapchainInner::Headless(headless) => Some(headless),
+        }
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.as_headless().is_some()
+    }
+
+    pub fn info(&self) -> &SwapchainInfo {
+        match &*self.inner {
+            SwapchainInner::Surface(surface) => &surface.info,
+            SwapchainInner::Headless(headless) => &headless.info,
+        }
+    }
+
+    pub fn surface_extent(&self) -> Extent2D {
+        match &*self.inner {
+            SwapchainInner::Surface(surface) => {
+                Extent2D::from_c(unsafe { daxa_sys::daxa_swp_get_surface_extent(surface.handle) })
+            }
+            SwapchainInner::Headless(headless) => headless.extent,
+        }
+    }
+
+    pub fn format(&self) -> Format {
+        match &*self.inner {
+            SwapchainInner::Surface(surface) => {
+                let format = unsafe { daxa_sys::daxa_swp_get_format(surface.handle) };
+                Format::try_from(format).unwrap_or(Format::UNDEFINED)
+            }
+            SwapchainInner::Headless(headless) => headless.format,
+        }
+    }
+
+    //recreates the swapchain images for the current surface extent, the format is selected again
+    //headless swapchains have no surface to follow, so for them this does nothing
+    pub fn resize(&self) -> std::result::Result<(), ResizeError> {
+        let SwapchainInner::Surface(surface) = &*self.inner else {
+            return Ok(());
+        };
+        let selector = surface.info.bind_format_selector();
+        unsafe {
+            DaxaError::check(
+                daxa_sys::daxa_swp_resize(surface.handle),
+                "resize",
+                surface.info.name(),
+            )
+            .map_err(ResizeError::Daxa)?;
+        }
+        //the surface can change its formats between resizes, e.g. when moved to another monitor
+        if !selector.accepted_any() {
+            return Err(ResizeError::NoSuitableFormat);
+        }
+        Ok(())
+    }
+
+    //the image stays owned by the swapchain, it must not be destroyed and is only valid until presented
+    pub fn acquire_next_image(&self) -> std::result::Result<ImageId, AcquireError> {
+        use crate::types::Result;
+        let surface = match &*self.inner {
+            SwapchainInner::Surface(surface) => surface,
+            SwapchainInner::Headless(headless) => return headless.acquire_next_image(),
+        };
+        unsafe {
+            let mut image = std::mem::zeroed();
+            let c_result = daxa_sys::daxa_swp_acquire_next_image(surface.handle, &mut image);
+
+            match Result::try_from(c_result) {
+                Ok(Result::Success | Result::SuboptimalKhr) => Ok(image),
+                Ok(Result::ErrorOutOfDateKhr) => Err(AcquireError::OutOfDate),
+                Ok(Result::ErrorDeviceLost) => Err(AcquireError::DeviceLost),
+                _ => Err(AcquireError::Daxa(DaxaError::new(
+                    c_result,
+                    "acquire_next_image",
+                    surface.info.name(),
+                ))),
+            }
+        }
+    }
+
+    //signalled once the acquired image is ready, the first submit of the frame waits on it
+    pub fn current_acquire_semaphore(&self) -> BinarySemaphore {
+        match &*self.inner {
+            SwapchainInner::Surface(surface) => unsafe {
+                let semaphore = daxa_sys::daxa_swp_current_acquire_semaphore(surface.handle);
+                surface.share_binary_semaphore(*semaphore, "acquire semaphore")
+            },
+            SwapchainInner::Headless(headless) => headless.current_slot().acquire_semaphore.clone(),
+        }
+    }
+
+    //the last submit of the frame signals this and present waits on it
+    pub fn current_present_semaphore(&self) -> BinarySemaphore {
+        match &*self.inner {
+            SwapchainInner::Surface(surface) => unsafe {
+                let semaphore = daxa_sys::daxa_swp_current_present_semaphore(surface.handle);
+                surface.share_binary_semaphore(*semaphore, "present semaphore")
+            },
+            SwapchainInner::Headless(headless) => headless.current_slot().present_semaphore.clone(),
+        }
+    }
+
+    //the value the last submit of the current frame should signal on the gpu timeline
+    pub fn current_cpu_timeline_value(&self) -> u64 {
+        match &*self.inner {
+            SwapchainInner::Surface(surface) => unsafe {
+                daxa_sys::daxa_swp_current_cpu_timeline_value(surface.handle)
+            },
+            SwapchainInner::Headless(headless) => headless.state().cpu_timeline,
+        }
+    }
+
+    //acquire waits on this before reusing a frame in flight, see max_allowed_frames_in_flight
+    pub fn gpu_timeline_semaphore(&self) -> TimelineSemaphore {
+        let surface = match &*self.inner {
+            SwapchainInner::Surface(surface) => surface,
+            SwapchainInner::Headless(headless) => return headless.gpu_timeline.clone(),
+        };
+        unsafe {
+            let semaphore = *daxa_sys::daxa_swp_gpu_timeline_semaphore(surface.handle);
+            //the swapchain keeps its own reference, the wrapper takes another it releases on drop
+            daxa_sys::daxa_timeline_semaphore_inc_refcnt(semaphore);
+            TimelineSemaphore::from_raw(
+                surface.device.clone(),
+                semaphore,
+                TimelineSemaphoreInfo {
+                    initial_value: 0,
+                    name: format!("{} gpu timeline", surface.info.name()),
+                },
+            )
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use std::ptr;
+use std::sync;
+use std::time;
+
+use crate::device::Device;
+use crate::types::*;
+
+#[derive(Clone, Debug, Default)]
+pub struct EventInfo {
+    pub name: String,
+}
+
+impl AsCInfo for EventInfo {
+    type C = daxa_sys::daxa_EventInfo;
+
+    fn as_c_info(&self) -> std::result::Result<CInfo<Self::C>, InvalidName> {
+        CInfo::new(&self.name, |name| daxa_sys::daxa_EventInfo { name })
+    }
+}
+
+struct EventHandle {
+    handle: daxa_sys::daxa_Event,
+    info: EventInfo,
+    device: Device,
+}
+
+impl Drop for EventHandle {
+    fn drop(&mut self) {
+        unsafe {
+            daxa_sys::daxa_event_dec_refcnt(self.handle);
+        }
+    }
+}
+
+//gpu side signal for split barriers, signalled and waited on within command recorders
+#[derive(Clone)]
+pub struct Event {
+    inner: sync::Arc<EventHandle>,
+}
+
+impl Event {
+    pub(crate) fn from_raw(device: Device, handle: daxa_sys::daxa_Event, info: EventInfo) -> Self {
+        Self {
+            inner: sync::Arc::new(EventHandle {
+                handle,
+                info,
+                device,
+            }),
+        }
+    }
+
+    pub(crate) fn handle(&self) -> &daxa_sys::daxa_Event {
+        &self.inner.handle
+    }
+
+    pub(crate) fn device(&self) -> &Device {
+        &self.inner.device
+    }
+
+    pub fn info(&self) -> &EventInfo {
+        &self.inner.info
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BinarySemaphoreInfo {
+    pub name: String,
+}
+
+impl AsCInfo for BinarySemaphoreInfo {
+    type C = daxa_sys::daxa_BinarySemaphoreInfo;
+
+    fn as_c_info(&self) -> std::result::Result<CInfo<Self::C>, InvalidName> {
+        CInfo::new(&self.name, |name| daxa_sys::daxa_BinarySemaphoreInfo {
+            name,
+        })
+    }
+}
+
+struct BinarySemaphoreHandle {
+    handle: daxa_sys::daxa_BinarySemaphore,
+    info: BinarySemaphoreInfo,
+    _device: Device,
+}
+
+impl Drop for BinarySemaphoreHandle {
+    fn drop(&mut self) {
+        unsafe {
+            daxa_sys::daxa_binary_semaphore_dec_refcnt(self.handle);
+        }
+    }
+}
+
+//orders queue operations against each other, for example acquire, submit and present
+#[derive(Clone)]
+pub struct BinarySemaphore {
+    inner: sync::Arc<BinarySemaphoreHandle>,
+}
+
+impl BinarySemaphore {
+    pub(crate) fn from_raw(
+        device: Device,
+        handle: daxa_sys::daxa_BinarySemaphore,
+        info: BinarySemaphoreInfo,
+    ) -> Self {
+        Self {
+            inner: sync::Arc::new(BinarySemaphoreHandle {
+                handle,
+                info,
+                _device: device,
+            }),
+        }
+    }
+
+    pub(crate) fn handle(&self) -> daxa_sys::daxa_BinarySemaphore {
+        self.inner.handle
+    }
+
+    pub fn info(&self) -> &BinarySemaphoreInfo {
+        &self.inner.info
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TimelineSemaphoreInfo {
+    pub initial_value: u64,
+    pub name: String,
+}
+
+impl AsCInfo for TimelineSemaphoreInfo {
+    type C = daxa_sys::daxa_TimelineSemaphoreInfo;
+
+    fn as_c_info(&self) -> std::result::Result<CInfo<Self::C>, InvalidName> {
+        CInfo::new(&self.name, |name| daxa_sys::daxa_TimelineSemaphoreInfo {
+            initial_value: self.initial_value,
+            name,
+        })
+    }
+}
+
+struct TimelineSemaphoreHandle {
+    handle: daxa_sys::daxa_TimelineSemaphore,
+    info: TimelineSemaphoreInfo,
+    device: Device,
+}
+
+impl Drop for TimelineSemaphoreHandle {
+    fn drop(&mut self) {
+        unsafe {
+            daxa_sys::daxa_timeline_semaphore_dec_refcnt(self.handle);
+        }
+    }
+}
+
+//host waits, signals and value queries on timeline semaphores need no external synchronization
+unsafe impl Send for TimelineSemaphoreHandle {}
+unsafe impl Sync for TimelineSemaphoreHandle {}
+
+//a monotonically increasing counter shared by the host and the gpu
+#[derive(Clone)]
+pub struct TimelineSemaphore {
+    inner: sync::Arc<TimelineSemaphoreHandle>,
+}
+
+impl TimelineSemaphore {
+    pub(crate) fn from_raw(
+        device: Device,
+        handle: daxa_sys::daxa_TimelineSemaphore,
+        info: TimelineSemaphoreInfo,
+    ) -> Self {
+        Self {
+            inner: sync::Arc::new(TimelineSemaphoreHandle {
+                handle,
+                info,
+                device,
+            }),
+        }
+    }
+
+    pub(crate) fn handle(&self) -> daxa_sys::daxa_TimelineSemaphore {
+        self.inner.handle
+    }
+
+    pub fn info(&self) -> &TimelineSemaphoreInfo {
+        &self.inner.info
+    }
+
+    pub(crate) fn device(&self) -> &Device {
+        &self.inner.device
+    }
+
+    pub fn value(&self) -> std::result::Result<u64, DaxaError> {
+        unsafe {
+            let mut value = 0;
+            DaxaError::check(
+                daxa_sys::daxa_timeline_semaphore_get_value(self.inner.handle, &mut value),
+                "get_value",
+                &self.inner.info.name,
+            )?;
+            Ok(value)
+        }
+    }
+
+    //host side signal, the value must be greater than the current one and any pending gpu signal
+    pub fn signal(&self, value: u64) -> std::result::Result<(), DaxaError> {
+        unsafe {
+            DaxaError::check(
+                daxa_sys::daxa_timeline_semaphore_set_value(self.inner.handle, value),
+                "signal",
+                &self.inner.info.name,
+            )
+        }
+    }
+
+    pub fn wait_for_value(
+        &self,
+        value: u64,
+        timeout: time::Duration,
+    ) -> std::result::Result<(), WaitError> {
+        let c_result = unsafe {
+            daxa_sys::daxa_timeline_semaphore_wait_for_value(
+                self.inner.handle,
+                value,
+                timeout_nanos(timeout),
+            )
+        };
+        WaitError::check(c_result, "wait_for_value", &self.inner.info.name)
+    }
+
+    //returns the index of a semaphore that reached its value, the first one if several did
+    pub fn wait_any(
+        semaphores: &[(TimelineSemaphore, u64)],
+        timeout: time::Duration,
+    ) -> std::result::Result<usize, WaitError> {
+        if semaphores.is_empty() {
+            return Err(WaitError::NoSemaphores);
+        }
+        Self::wait_many(
+            semaphores,
+            daxa_sys::VkSemaphoreWaitFlagBits_VK_SEMAPHORE_WAIT_ANY_BIT,
+            timeout,
+            "wait_any",
+        )?;
+        for (index, (semaphore, value)) in semaphores.iter().enumerate() {
+            if semaphore.value().map_err(WaitError::Daxa)? >= *value {
+                return Ok(index);
+            }
+        }
+        //the driver returned early, which vulkan does not allow
+        let device = semaphores[0].0.device();
+        Err(WaitError::Daxa(DaxaError::new(
+            Result::ErrorUnknown as i32,
+            "wait_any",
+            device.info().name(),
+        )))
+    }
+
+    pub fn wait_all(
+        semaphores: &[(TimelineSemaphore, u64)],
+        timeout: time::Duration,
+    ) -> std::result::Result<(), WaitError> {
+        if semaphores.is_empty() {
+            return Ok(());
+        }
+        Self::wait_many(semaphores, 0, timeout, "wait_all")
+    }
+
+    fn wait_many(
+        semaphores: &[(TimelineSemaphore, u64)],
+        flags: daxa_sys::VkSemaphoreWaitFlags,
+        timeout: time::Duration,
+        operation: &'static str,
+    ) -> std::result::Result<(), WaitError> {
+        let device = &semaphores[0].0.inner.device;
+        //vkWaitSemaphores only waits on semaphores of a single device
+        if semaphores
+            .iter()
+            .any(|(semaphore, _)| semaphore.inner.device.vk_device() != device.vk_device())
+        {
+            return Err(WaitError::MixedDevices);
+        }
+        let Some(wait_semaphores) = device.vk_wait_semaphores() else {
+            return Err(WaitError::Daxa(DaxaError::new(
+                daxa_sys::daxa_Result_DAXA_RESULT_ERROR_EXTENSION_NOT_PRESENT,
+                operation,
+                device.info().name(),
+            )));
+        };
+
+        let (vk_semaphores, values): (Vec<_>, Vec<_>) = semaphores
+            .iter()
+            .map(|(semaphore, value)| unsafe {
+                (
+                    daxa_sys::daxa_timeline_semaphore_get_vk_semaphore(semaphore.inner.handle),
+                    *value,
+                )
+            })
+            .unzip();
+        let wait_info = daxa_sys::VkSemaphoreWaitInfo {
+            sType: daxa_sys::VkStructureType_VK_STRUCTURE_TYPE_SEMAPHORE_WAIT_INFO,
+            pNext: ptr::null(),
+            flags,
+            semaphoreCount: vk_semaphores.len() as u32,
+            pSemaphores: vk_semaphores.as_ptr(),
+            pValues: values.as_ptr(),
+        };
+        let c_result =
+            unsafe { wait_semaphores(device.vk_device(), &wait_info, timeout_nanos(timeout)) };
+        //vulkan and daxa share their result codes
+        WaitError::check(c_result, operation, device.info().name())
+    }
+}
+
+//vulkan takes the timeout in nanoseconds, anything past u64::MAX is as good as forever
+fn timeout_nanos(timeout: time::Duration) -> u64 {
+    timeout.as_nanos().min(u64::MAX as u128) as u64
+}
+
+#[derive(Clone, Debug)]
+pub enum WaitError {
+    //the value was not reached in time, unlike the other variants this is not a failure of the device
+    Timeout,
+    //wait_any was given no semaphores, so none of them can ever be reached
+    NoSemaphores,
+    //the semaphores of one wait have to come from the same device
+    MixedDevices,
+    DeviceLost,
+    Daxa(DaxaError),
+}
+
+impl WaitError {
+    fn check(
+        code: i32,
+        operation: &'static str,
+        object_name: &str,
+    ) -> std::result::Result<(), WaitError> {
+        match Result::try_from(code) {
+            Ok(Result::Success) => Ok(()),
+            Ok(Result::Timeout) => Err(WaitError::Timeout),
+            Ok(Result::ErrorDeviceLost) => Err(WaitError::DeviceLost),
+            _ => Err(WaitError::Daxa(DaxaError::new(
+                code,
+                operation,
+                object_name,
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for WaitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for WaitError {}
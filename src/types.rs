@@ -1,15 +1,279 @@
 use bitflags::bitflags;
-use std::{marker::PhantomData, mem, os};
+use bytemuck::Pod;
+use std::{ffi, marker::PhantomData, mem, ops, os, sync};
 
-use crate::device::{Device, DeviceType};
+use crate::command_recorder::ExecutableCommandList;
+use crate::device::{Device, DeviceType, Zombie};
+use crate::swapchain::Swapchain;
+use crate::sync::{BinarySemaphore, TimelineSemaphore};
 
 #[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Result {
     Success = daxa_sys::daxa_Result_DAXA_RESULT_SUCCESS,
+    NotReady = daxa_sys::daxa_Result_DAXA_RESULT_NOT_READY,
+    Timeout = daxa_sys::daxa_Result_DAXA_RESULT_TIMEOUT,
+    EventSet = daxa_sys::daxa_Result_DAXA_RESULT_EVENT_SET,
+    EventReset = daxa_sys::daxa_Result_DAXA_RESULT_EVENT_RESET,
+    Incomplete = daxa_sys::daxa_Result_DAXA_RESULT_INCOMPLETE,
+    ErrorOutOfHostMemory = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_OUT_OF_HOST_MEMORY,
+    ErrorOutOfDeviceMemory = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_OUT_OF_DEVICE_MEMORY,
+    ErrorInitializationFailed = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INITIALIZATION_FAILED,
+    ErrorDeviceLost = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_DEVICE_LOST,
+    ErrorMemoryMapFailed = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_MEMORY_MAP_FAILED,
+    ErrorLayerNotPresent = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_LAYER_NOT_PRESENT,
+    ErrorExtensionNotPresent = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_EXTENSION_NOT_PRESENT,
+    ErrorFeatureNotPresent = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_FEATURE_NOT_PRESENT,
+    ErrorIncompatibleDriver = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INCOMPATIBLE_DRIVER,
+    ErrorTooManyObjects = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_TOO_MANY_OBJECTS,
+    ErrorFormatNotSupported = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_FORMAT_NOT_SUPPORTED,
+    ErrorFragmentedPool = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_FRAGMENTED_POOL,
+    ErrorUnknown = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_UNKNOWN,
+    ErrorOutOfPoolMemory = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_OUT_OF_POOL_MEMORY,
+    ErrorInvalidExternalHandle = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INVALID_EXTERNAL_HANDLE,
+    ErrorFragmentation = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_FRAGMENTATION,
+    ErrorInvalidOpaqueCaptureAddress = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INVALID_OPAQUE_CAPTURE_ADDRESS,
+    PipelineCompileRequired = daxa_sys::daxa_Result_DAXA_RESULT_PIPELINE_COMPILE_REQUIRED,
+    ErrorSurfaceLostKhr = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_SURFACE_LOST_KHR,
+    ErrorNativeWindowInUseKhr = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_NATIVE_WINDOW_IN_USE_KHR,
+    SuboptimalKhr = daxa_sys::daxa_Result_DAXA_RESULT_SUBOPTIMAL_KHR,
+    ErrorOutOfDateKhr = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_OUT_OF_DATE_KHR,
+    ErrorIncompatibleDisplayKhr = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INCOMPATIBLE_DISPLAY_KHR,
+    ErrorValidationFailedExt = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_VALIDATION_FAILED_EXT,
+    ErrorInvalidShaderNv = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INVALID_SHADER_NV,
+    ErrorImageUsageNotSupportedKhr = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_IMAGE_USAGE_NOT_SUPPORTED_KHR,
+    ErrorInvalidDrmFormatModifierPlaneLayoutExt = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INVALID_DRM_FORMAT_MODIFIER_PLANE_LAYOUT_EXT,
+    ErrorNotPermittedKhr = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_NOT_PERMITTED_KHR,
+    ErrorFullScreenExclusiveModeLostExt = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT,
+    ThreadIdleKhr = daxa_sys::daxa_Result_DAXA_RESULT_THREAD_IDLE_KHR,
+    ThreadDoneKhr = daxa_sys::daxa_Result_DAXA_RESULT_THREAD_DONE_KHR,
+    OperationDeferredKhr = daxa_sys::daxa_Result_DAXA_RESULT_OPERATION_DEFERRED_KHR,
+    OperationNotDeferredKhr = daxa_sys::daxa_Result_DAXA_RESULT_OPERATION_NOT_DEFERRED_KHR,
+    ErrorCompressionExhaustedExt = daxa_sys::daxa_Result_DAXA_RESULT_ERROR_COMPRESSION_EXHAUSTED_EXT,
     MissingExtension = daxa_sys::daxa_Result_DAXA_RESULT_MISSING_EXTENSION,
+    InvalidBufferId = daxa_sys::daxa_Result_DAXA_RESULT_INVALID_BUFFER_ID,
+    InvalidImageId = daxa_sys::daxa_Result_DAXA_RESULT_INVALID_IMAGE_ID,
+    InvalidImageViewId = daxa_sys::daxa_Result_DAXA_RESULT_INVALID_IMAGE_VIEW_ID,
+    InvalidSamplerId = daxa_sys::daxa_Result_DAXA_RESULT_INVALID_SAMPLER_ID,
+    BufferDoubleFree = daxa_sys::daxa_Result_DAXA_RESULT_BUFFER_DOUBLE_FREE,
+    ImageDoubleFree = daxa_sys::daxa_Result_DAXA_RESULT_IMAGE_DOUBLE_FREE,
+    ImageViewDoubleFree = daxa_sys::daxa_Result_DAXA_RESULT_IMAGE_VIEW_DOUBLE_FREE,
+    SamplerDoubleFree = daxa_sys::daxa_Result_DAXA_RESULT_SAMPLER_DOUBLE_FREE,
+    InvalidBufferInfo = daxa_sys::daxa_Result_DAXA_RESULT_INVALID_BUFFER_INFO,
+    InvalidImageInfo = daxa_sys::daxa_Result_DAXA_RESULT_INVALID_IMAGE_INFO,
+    InvalidImageViewInfo = daxa_sys::daxa_Result_DAXA_RESULT_INVALID_IMAGE_VIEW_INFO,
+    InvalidSamplerInfo = daxa_sys::daxa_Result_DAXA_RESULT_INVALID_SAMPLER_INFO,
+    CommandListCompleted = daxa_sys::daxa_Result_DAXA_RESULT_COMMAND_LIST_COMPLETED,
+    CommandListNotCompleted = daxa_sys::daxa_Result_DAXA_RESULT_COMMAND_LIST_NOT_COMPLETED,
+    InvalidClearValue = daxa_sys::daxa_Result_DAXA_RESULT_INVALID_CLEAR_VALUE,
+    BufferNotHostVisible = daxa_sys::daxa_Result_DAXA_RESULT_BUFFER_NOT_HOST_VISIBLE,
+    BufferNotDeviceVisible = daxa_sys::daxa_Result_DAXA_RESULT_BUFFER_NOT_DEVICE_VISIBLE,
+    IncompleteCommandList = daxa_sys::daxa_Result_DAXA_RESULT_INCOMPLETE_COMMAND_LIST,
+    DeviceDoesNotSupportBufferCount = daxa_sys::daxa_Result_DAXA_RESULT_DEVICE_DOES_NOT_SUPPORT_BUFFER_COUNT,
+    DeviceDoesNotSupportImageCount = daxa_sys::daxa_Result_DAXA_RESULT_DEVICE_DOES_NOT_SUPPORT_IMAGE_COUNT,
+    DeviceDoesNotSupportSamplerCount = daxa_sys::daxa_Result_DAXA_RESULT_DEVICE_DOES_NOT_SUPPORT_SAMPLER_COUNT,
+    FailedToCreateNullBuffer = daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_NULL_BUFFER,
+    FailedToCreateNullImage = daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_NULL_IMAGE,
+    FailedToCreateNullImageView = daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_NULL_IMAGE_VIEW,
+    FailedToCreateNullSampler = daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_NULL_SAMPLER,
+    FailedToCreateBuffer = daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_BUFFER,
+    FailedToCreateImage = daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_IMAGE,
+    FailedToCreateImageView = daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_IMAGE_VIEW,
+    FailedToCreateDefaultImageView = daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_DEFAULT_IMAGE_VIEW,
+    FailedToCreateSampler = daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_SAMPLER,
+    FailedToCreateBdaBuffer = daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_BDA_BUFFER,
+    FailedToSubmitDeviceInitCommands = daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_SUBMIT_DEVICE_INIT_COMMANDS,
+    InvalidBufferRange = daxa_sys::daxa_Result_DAXA_RESULT_INVALID_BUFFER_RANGE,
+    InvalidBufferOffset = daxa_sys::daxa_Result_DAXA_RESULT_INVALID_BUFFER_OFFSET,
+    NoSuitableFormatFound = daxa_sys::daxa_Result_DAXA_RESULT_NO_SUITABLE_FORMAT_FOUND,
+    RangeOutOfBounds = daxa_sys::daxa_Result_DAXA_RESULT_RANGE_OUT_OF_BOUNDS,
+    NoSuitableDeviceFound = daxa_sys::daxa_Result_DAXA_RESULT_NO_SUITABLE_DEVICE_FOUND,
+    ExceededMaxBuffers = daxa_sys::daxa_Result_DAXA_RESULT_EXCEEDED_MAX_BUFFERS,
+    ExceededMaxImages = daxa_sys::daxa_Result_DAXA_RESULT_EXCEEDED_MAX_IMAGES,
+    ExceededMaxImageViews = daxa_sys::daxa_Result_DAXA_RESULT_EXCEEDED_MAX_IMAGE_VIEWS,
+    ExceededMaxSamplers = daxa_sys::daxa_Result_DAXA_RESULT_EXCEEDED_MAX_SAMPLERS,
 }
 
+impl TryFrom<i32> for Result {
+    type Error = i32;
+
+    //daxa may hand back codes newer than these bindings, so never transmute a raw code
+    fn try_from(code: i32) -> std::result::Result<Self, i32> {
+        use Result::*;
+        Ok(match code {
+            daxa_sys::daxa_Result_DAXA_RESULT_SUCCESS => Success,
+            daxa_sys::daxa_Result_DAXA_RESULT_NOT_READY => NotReady,
+            daxa_sys::daxa_Result_DAXA_RESULT_TIMEOUT => Timeout,
+            daxa_sys::daxa_Result_DAXA_RESULT_EVENT_SET => EventSet,
+            daxa_sys::daxa_Result_DAXA_RESULT_EVENT_RESET => EventReset,
+            daxa_sys::daxa_Result_DAXA_RESULT_INCOMPLETE => Incomplete,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_OUT_OF_HOST_MEMORY => ErrorOutOfHostMemory,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_OUT_OF_DEVICE_MEMORY => ErrorOutOfDeviceMemory,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INITIALIZATION_FAILED => ErrorInitializationFailed,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_DEVICE_LOST => ErrorDeviceLost,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_MEMORY_MAP_FAILED => ErrorMemoryMapFailed,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_LAYER_NOT_PRESENT => ErrorLayerNotPresent,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_EXTENSION_NOT_PRESENT => ErrorExtensionNotPresent,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_FEATURE_NOT_PRESENT => ErrorFeatureNotPresent,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INCOMPATIBLE_DRIVER => ErrorIncompatibleDriver,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_TOO_MANY_OBJECTS => ErrorTooManyObjects,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_FORMAT_NOT_SUPPORTED => ErrorFormatNotSupported,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_FRAGMENTED_POOL => ErrorFragmentedPool,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_UNKNOWN => ErrorUnknown,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_OUT_OF_POOL_MEMORY => ErrorOutOfPoolMemory,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INVALID_EXTERNAL_HANDLE => ErrorInvalidExternalHandle,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_FRAGMENTATION => ErrorFragmentation,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INVALID_OPAQUE_CAPTURE_ADDRESS => ErrorInvalidOpaqueCaptureAddress,
+            daxa_sys::daxa_Result_DAXA_RESULT_PIPELINE_COMPILE_REQUIRED => PipelineCompileRequired,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_SURFACE_LOST_KHR => ErrorSurfaceLostKhr,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_NATIVE_WINDOW_IN_USE_KHR => ErrorNativeWindowInUseKhr,
+            daxa_sys::daxa_Result_DAXA_RESULT_SUBOPTIMAL_KHR => SuboptimalKhr,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_OUT_OF_DATE_KHR => ErrorOutOfDateKhr,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INCOMPATIBLE_DISPLAY_KHR => ErrorIncompatibleDisplayKhr,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_VALIDATION_FAILED_EXT => ErrorValidationFailedExt,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INVALID_SHADER_NV => ErrorInvalidShaderNv,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_IMAGE_USAGE_NOT_SUPPORTED_KHR => ErrorImageUsageNotSupportedKhr,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_INVALID_DRM_FORMAT_MODIFIER_PLANE_LAYOUT_EXT => ErrorInvalidDrmFormatModifierPlaneLayoutExt,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_NOT_PERMITTED_KHR => ErrorNotPermittedKhr,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT => ErrorFullScreenExclusiveModeLostExt,
+            daxa_sys::daxa_Result_DAXA_RESULT_THREAD_IDLE_KHR => ThreadIdleKhr,
+            daxa_sys::daxa_Result_DAXA_RESULT_THREAD_DONE_KHR => ThreadDoneKhr,
+            daxa_sys::daxa_Result_DAXA_RESULT_OPERATION_DEFERRED_KHR => OperationDeferredKhr,
+            daxa_sys::daxa_Result_DAXA_RESULT_OPERATION_NOT_DEFERRED_KHR => OperationNotDeferredKhr,
+            daxa_sys::daxa_Result_DAXA_RESULT_ERROR_COMPRESSION_EXHAUSTED_EXT => ErrorCompressionExhaustedExt,
+            daxa_sys::daxa_Result_DAXA_RESULT_MISSING_EXTENSION => MissingExtension,
+            daxa_sys::daxa_Result_DAXA_RESULT_INVALID_BUFFER_ID => InvalidBufferId,
+            daxa_sys::daxa_Result_DAXA_RESULT_INVALID_IMAGE_ID => InvalidImageId,
+            daxa_sys::daxa_Result_DAXA_RESULT_INVALID_IMAGE_VIEW_ID => InvalidImageViewId,
+            daxa_sys::daxa_Result_DAXA_RESULT_INVALID_SAMPLER_ID => InvalidSamplerId,
+            daxa_sys::daxa_Result_DAXA_RESULT_BUFFER_DOUBLE_FREE => BufferDoubleFree,
+            daxa_sys::daxa_Result_DAXA_RESULT_IMAGE_DOUBLE_FREE => ImageDoubleFree,
+            daxa_sys::daxa_Result_DAXA_RESULT_IMAGE_VIEW_DOUBLE_FREE => ImageViewDoubleFree,
+            daxa_sys::daxa_Result_DAXA_RESULT_SAMPLER_DOUBLE_FREE => SamplerDoubleFree,
+            daxa_sys::daxa_Result_DAXA_RESULT_INVALID_BUFFER_INFO => InvalidBufferInfo,
+            daxa_sys::daxa_Result_DAXA_RESULT_INVALID_IMAGE_INFO => InvalidImageInfo,
+            daxa_sys::daxa_Result_DAXA_RESULT_INVALID_IMAGE_VIEW_INFO => InvalidImageViewInfo,
+            daxa_sys::daxa_Result_DAXA_RESULT_INVALID_SAMPLER_INFO => InvalidSamplerInfo,
+            daxa_sys::daxa_Result_DAXA_RESULT_COMMAND_LIST_COMPLETED => CommandListCompleted,
+            daxa_sys::daxa_Result_DAXA_RESULT_COMMAND_LIST_NOT_COMPLETED => CommandListNotCompleted,
+            daxa_sys::daxa_Result_DAXA_RESULT_INVALID_CLEAR_VALUE => InvalidClearValue,
+            daxa_sys::daxa_Result_DAXA_RESULT_BUFFER_NOT_HOST_VISIBLE => BufferNotHostVisible,
+            daxa_sys::daxa_Result_DAXA_RESULT_BUFFER_NOT_DEVICE_VISIBLE => BufferNotDeviceVisible,
+            daxa_sys::daxa_Result_DAXA_RESULT_INCOMPLETE_COMMAND_LIST => IncompleteCommandList,
+            daxa_sys::daxa_Result_DAXA_RESULT_DEVICE_DOES_NOT_SUPPORT_BUFFER_COUNT => DeviceDoesNotSupportBufferCount,
+            daxa_sys::daxa_Result_DAXA_RESULT_DEVICE_DOES_NOT_SUPPORT_IMAGE_COUNT => DeviceDoesNotSupportImageCount,
+            daxa_sys::daxa_Result_DAXA_RESULT_DEVICE_DOES_NOT_SUPPORT_SAMPLER_COUNT => DeviceDoesNotSupportSamplerCount,
+            daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_NULL_BUFFER => FailedToCreateNullBuffer,
+            daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_NULL_IMAGE => FailedToCreateNullImage,
+            daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_NULL_IMAGE_VIEW => FailedToCreateNullImageView,
+            daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_NULL_SAMPLER => FailedToCreateNullSampler,
+            daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_BUFFER => FailedToCreateBuffer,
+            daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_IMAGE => FailedToCreateImage,
+            daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_IMAGE_VIEW => FailedToCreateImageView,
+            daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_DEFAULT_IMAGE_VIEW => FailedToCreateDefaultImageView,
+            daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_SAMPLER => FailedToCreateSampler,
+            daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_CREATE_BDA_BUFFER => FailedToCreateBdaBuffer,
+            daxa_sys::daxa_Result_DAXA_RESULT_FAILED_TO_SUBMIT_DEVICE_INIT_COMMANDS => FailedToSubmitDeviceInitCommands,
+            daxa_sys::daxa_Result_DAXA_RESULT_INVALID_BUFFER_RANGE => InvalidBufferRange,
+            daxa_sys::daxa_Result_DAXA_RESULT_INVALID_BUFFER_OFFSET => InvalidBufferOffset,
+            daxa_sys::daxa_Result_DAXA_RESULT_NO_SUITABLE_FORMAT_FOUND => NoSuitableFormatFound,
+            daxa_sys::daxa_Result_DAXA_RESULT_RANGE_OUT_OF_BOUNDS => RangeOutOfBounds,
+            daxa_sys::daxa_Result_DAXA_RESULT_NO_SUITABLE_DEVICE_FOUND => NoSuitableDeviceFound,
+            daxa_sys::daxa_Result_DAXA_RESULT_EXCEEDED_MAX_BUFFERS => ExceededMaxBuffers,
+            daxa_sys::daxa_Result_DAXA_RESULT_EXCEEDED_MAX_IMAGES => ExceededMaxImages,
+            daxa_sys::daxa_Result_DAXA_RESULT_EXCEEDED_MAX_IMAGE_VIEWS => ExceededMaxImageViews,
+            daxa_sys::daxa_Result_DAXA_RESULT_EXCEEDED_MAX_SAMPLERS => ExceededMaxSamplers,
+            _ => return Err(code),
+        })
+    }
+}
+
+impl Result {
+    pub fn is_success(self) -> bool {
+        self == Result::Success
+    }
+
+    //negative codes are errors, positive ones are statuses like timeouts or suboptimal swapchains
+    pub fn is_error(self) -> bool {
+        (self as i32) < 0
+    }
+}
+
+//never returned by daxa, marks calls that were refused before reaching it
+const INVALID_NAME_CODE: i32 = i32::MIN;
+
+//a failed daxa call, with what was being done and to which object
+#[derive(Clone, Debug)]
+pub struct DaxaError {
+    code: i32,
+    operation: &'static str,
+    object_name: String,
+}
+
+impl DaxaError {
+    pub fn new(code: i32, operation: &'static str, object_name: impl Into<String>) -> Self {
+        Self {
+            code,
+            operation,
+            object_name: object_name.into(),
+        }
+    }
+
+    pub(crate) fn check(
+        code: i32,
+        operation: &'static str,
+        object_name: &str,
+    ) -> std::result::Result<(), DaxaError> {
+        match Result::try_from(code) {
+            Ok(Result::Success) => Ok(()),
+            _ => Err(DaxaError::new(code, operation, object_name)),
+        }
+    }
+
+    //daxa reads names as c strings, so a name with an interior nul is refused before the call
+    pub(crate) fn invalid_name(operation: &'static str, object_name: &str) -> Self {
+        Self::new(INVALID_NAME_CODE, operation, object_name)
+    }
+
+    pub fn is_invalid_name(&self) -> bool {
+        self.code == INVALID_NAME_CODE
+    }
+
+    //None if the code is unknown to these bindings
+    pub fn result(&self) -> std::option::Option<Result> {
+        Result::try_from(self.code).ok()
+    }
+
+    pub fn code(&self) -> i32 {
+        self.code
+    }
+
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    pub fn object_name(&self) -> &str {
+        &self.object_name
+    }
+}
+
+impl std::fmt::Display for DaxaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} failed", self.operation)?;
+        if !self.object_name.is_empty() {
+            write!(f, " for \"{}\"", self.object_name)?;
+        }
+        match self.result() {
+            Some(result) => write!(f, ": {:?} ({})", result, self.code),
+            None if self.is_invalid_name() => write!(f, ": the name contains a nul byte"),
+            None => write!(f, ": unrecognized result code {}", self.code),
+        }
+    }
+}
+impl std::error::Error for DaxaError {}
+
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ImageLayout {
     Undefined = daxa_sys::daxa_ImageLayout_DAXA_IMAGE_LAYOUT_UNDEFINED,
     General = daxa_sys::daxa_ImageLayout_DAXA_IMAGE_LAYOUT_GENERAL,
@@ -45,31 +309,158 @@ pub struct Vec4<T> {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct ImageMipArraySlice {
-    base_mip_level: u32,
-    level_count: u32,
-    base_array_layer: u32,
-    layer_count: u32,
+    pub base_mip_level: u32,
+    pub level_count: u32,
+    pub base_array_layer: u32,
+    pub layer_count: u32,
+}
+
+impl Default for ImageMipArraySlice {
+    fn default() -> Self {
+        Self {
+            base_mip_level: 0,
+            level_count: 1,
+            base_array_layer: 0,
+            layer_count: 1,
+        }
+    }
 }
+
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct ImageArraySlice {
-    mip_level: u32,
-    base_array_layer: u32,
-    layer_count: u32,
+    pub mip_level: u32,
+    pub base_array_layer: u32,
+    pub layer_count: u32,
+}
+
+impl Default for ImageArraySlice {
+    fn default() -> Self {
+        Self {
+            mip_level: 0,
+            base_array_layer: 0,
+            layer_count: 1,
+        }
+    }
 }
 
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct ImageSlice {
-    mip_level: u32,
-    array_layer: u32,
+    pub mip_level: u32,
+    pub array_layer: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Offset3D {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Offset3D {
+    pub(crate) fn as_c(&self) -> daxa_sys::VkOffset3D {
+        unsafe { mem::transmute(*self) }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Extent2D {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Extent2D {
+    pub(crate) fn from_c(extent: daxa_sys::VkExtent2D) -> Self {
+        unsafe { mem::transmute(extent) }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Extent3D {
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+}
+
+impl Extent3D {
+    pub(crate) fn as_c(&self) -> daxa_sys::VkExtent3D {
+        unsafe { mem::transmute(*self) }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect2D {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClearValue {
+    Float([f32; 4]),
+    Int([i32; 4]),
+    Uint([u32; 4]),
+    DepthStencil { depth: f32, stencil: u32 },
+}
+
+impl Default for ClearValue {
+    fn default() -> Self {
+        ClearValue::Float([0.0; 4])
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DepthValue {
+    depth: f32,
+    stencil: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+union ClearValueValues {
+    float32: [f32; 4],
+    int32: [i32; 4],
+    uint32: [u32; 4],
+    depth_stencil: DepthValue,
+}
+
+//mirrors daxa's variant layout, the index selects the active union member
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct CClearValue {
+    values: ClearValueValues,
+    index: u8,
+}
+
+impl ClearValue {
+    pub(crate) fn as_c(&self) -> daxa_sys::daxa_ClearValue {
+        let (values, index) = match *self {
+            ClearValue::Float(float32) => (ClearValueValues { float32 }, 0),
+            ClearValue::Int(int32) => (ClearValueValues { int32 }, 1),
+            ClearValue::Uint(uint32) => (ClearValueValues { uint32 }, 2),
+            ClearValue::DepthStencil { depth, stencil } => (
+                ClearValueValues {
+                    depth_stencil: DepthValue { depth, stencil },
+                },
+                3,
+            ),
+        };
+        unsafe { mem::transmute(CClearValue { values, index }) }
+    }
 }
 
 bitflags! {
     #[derive(Default)]
-    pub struct PipelineStageFlags: i32 {
+    pub struct PipelineStageFlags: u64 {
         const TOP_OF_PIPE_BIT = daxa_sys::VK_PIPELINE_STAGE_2_TOP_OF_PIPE_BIT;
         const DRAW_INDIRECT_BIT = daxa_sys::VK_PIPELINE_STAGE_2_DRAW_INDIRECT_BIT;
         const VERTEX_INPUT_BIT = daxa_sys::VK_PIPELINE_STAGE_2_VERTEX_INPUT_BIT;
@@ -107,7 +498,34 @@ bitflags! {
 }
 
 bitflags! {
-    pub struct ImageViewType: i32 {
+    #[derive(Default)]
+    pub struct AccessTypeFlags: u64 {
+        const NONE = daxa_sys::VK_ACCESS_2_NONE;
+        const READ = daxa_sys::VK_ACCESS_2_MEMORY_READ_BIT;
+        const WRITE = daxa_sys::VK_ACCESS_2_MEMORY_WRITE_BIT;
+        const READ_WRITE = Self::READ.bits | Self::WRITE.bits;
+    }
+}
+
+//which stages touch memory and how, the two sides of every barrier
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Access {
+    pub stages: PipelineStageFlags,
+    pub access_type: AccessTypeFlags,
+}
+
+impl Access {
+    pub const fn new(stages: PipelineStageFlags, access_type: AccessTypeFlags) -> Self {
+        Self {
+            stages,
+            access_type,
+        }
+    }
+}
+
+bitflags! {
+    pub struct ImageViewType: u32 {
         const ONE_DIM = daxa_sys::VkImageViewType_VK_IMAGE_VIEW_TYPE_1D;
         const TWO_DIM = daxa_sys::VkImageViewType_VK_IMAGE_VIEW_TYPE_2D;
         const THREE_DIM = daxa_sys::VkImageViewType_VK_IMAGE_VIEW_TYPE_3D;
@@ -119,7 +537,7 @@ bitflags! {
 }
 
 bitflags! {
-    pub struct Filter: i32 {
+    pub struct Filter: u32 {
         const NEAREST = daxa_sys::VkFilter_VK_FILTER_NEAREST;
         const LINEAR = daxa_sys::VkFilter_VK_FILTER_LINEAR;
         const CUBIC_EXT = daxa_sys::VkFilter_VK_FILTER_CUBIC_EXT;
@@ -128,7 +546,8 @@ bitflags! {
 }
 
 bitflags! {
-    pub struct ImageCreateFlags: i32 {
+    #[derive(Default)]
+    pub struct ImageCreateFlags: u32 {
         const SPARSE_BINDING = daxa_sys::VkImageCreateFlagBits_VK_IMAGE_CREATE_SPARSE_BINDING_BIT;
         const SPARSE_RESIDENCY = daxa_sys::VkImageCreateFlagBits_VK_IMAGE_CREATE_SPARSE_RESIDENCY_BIT;
         const SPARSE_ALIASED = daxa_sys::VkImageCreateFlagBits_VK_IMAGE_CREATE_SPARSE_ALIASED_BIT;
@@ -151,7 +570,8 @@ bitflags! {
     }
 }
 bitflags! {
-    pub struct ImageUsageFlags: i32 {
+    #[derive(Default)]
+    pub struct ImageUsageFlags: u32 {
         const TRANSFER_SRC = daxa_sys::VkImageUsageFlagBits_VK_IMAGE_USAGE_TRANSFER_SRC_BIT;
         const TRANSFER_DST = daxa_sys::VkImageUsageFlagBits_VK_IMAGE_USAGE_TRANSFER_DST_BIT;
         const SAMPLED = daxa_sys::VkImageUsageFlagBits_VK_IMAGE_USAGE_SAMPLED_BIT;
@@ -172,6 +592,7 @@ bitflags! {
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SamplerAddressMode {
     Repeat = daxa_sys::VkSamplerAddressMode_VK_SAMPLER_ADDRESS_MODE_REPEAT,
     MirroredRepeat = daxa_sys::VkSamplerAddressMode_VK_SAMPLER_ADDRESS_MODE_MIRRORED_REPEAT,
@@ -182,30 +603,173 @@ pub enum SamplerAddressMode {
 }
 
 #[repr(u32)]
-enum ReductionMode {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReductionMode {
     WeightedAverage = daxa_sys::VkSamplerReductionMode_VK_SAMPLER_REDUCTION_MODE_WEIGHTED_AVERAGE,
     Min = daxa_sys::VkSamplerReductionMode_VK_SAMPLER_REDUCTION_MODE_MIN,
     Max = daxa_sys::VkSamplerReductionMode_VK_SAMPLER_REDUCTION_MODE_MAX,
 }
 
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BorderColor {
+    FloatTransparentBlack = daxa_sys::VkBorderColor_VK_BORDER_COLOR_FLOAT_TRANSPARENT_BLACK,
+    IntTransparentBlack = daxa_sys::VkBorderColor_VK_BORDER_COLOR_INT_TRANSPARENT_BLACK,
+    FloatOpaqueBlack = daxa_sys::VkBorderColor_VK_BORDER_COLOR_FLOAT_OPAQUE_BLACK,
+    IntOpaqueBlack = daxa_sys::VkBorderColor_VK_BORDER_COLOR_INT_OPAQUE_BLACK,
+    FloatOpaqueWhite = daxa_sys::VkBorderColor_VK_BORDER_COLOR_FLOAT_OPAQUE_WHITE,
+    IntOpaqueWhite = daxa_sys::VkBorderColor_VK_BORDER_COLOR_INT_OPAQUE_WHITE,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Extent {
     OneDim(u32),
     TwoDim(u32, u32),
     ThreeDim(u32, u32, u32),
 }
 
+impl Extent {
+    //daxa splits the extent into a dimension count and a full 3d size
+    pub(crate) fn as_c(&self) -> (u32, daxa_sys::VkExtent3D) {
+        let (dimensions, width, height, depth) = match *self {
+            Extent::OneDim(width) => (1, width, 1, 1),
+            Extent::TwoDim(width, height) => (2, width, height, 1),
+            Extent::ThreeDim(width, height, depth) => (3, width, height, depth),
+        };
+        (
+            dimensions,
+            daxa_sys::VkExtent3D {
+                width,
+                height,
+                depth,
+            },
+        )
+    }
+}
+
+//timeline entries pair a semaphore with the value to wait for or signal
+#[derive(Clone, Copy, Default)]
 pub struct CommandSubmitInfo<'a> {
+    pub wait_stages: PipelineStageFlags,
+    pub command_lists: &'a [ExecutableCommandList],
+    pub wait_binary_semaphores: &'a [BinarySemaphore],
+    pub signal_binary_semaphores: &'a [BinarySemaphore],
+    pub wait_timeline_semaphores: &'a [(TimelineSemaphore, u64)],
+    pub signal_timeline_semaphores: &'a [(TimelineSemaphore, u64)],
+}
+
+#[repr(C)]
+pub(crate) struct TimelinePair {
+    semaphore: daxa_sys::daxa_TimelineSemaphore,
+    value: u64,
+}
+
+#[repr(C)]
+pub(crate) struct CCommandSubmitInfo {
     wait_stages: PipelineStageFlags,
-    cmd_recorders: &'a [CommandRecorder],
-    wait_binary_semaphores: &'a [BinarySemaphore],
-    signal_binary_semaphores: &'a [BinarySemaphore],
-    wait_timeline_semaphores: &'a [BinarySemaphore],
-    signal_timeline_semaphores: &'a [BinarySemaphore],
+    command_lists: *const daxa_sys::daxa_ExecutableCommandList,
+    command_list_count: u64,
+    wait_binary_semaphores: *const daxa_sys::daxa_BinarySemaphore,
+    wait_binary_semaphore_count: u64,
+    signal_binary_semaphores: *const daxa_sys::daxa_BinarySemaphore,
+    signal_binary_semaphore_count: u64,
+    wait_timeline_semaphores: *const TimelinePair,
+    wait_timeline_semaphore_count: u64,
+    signal_timeline_semaphores: *const TimelinePair,
+    signal_timeline_semaphore_count: u64,
+}
+
+//daxa wants arrays of raw handles, so the lowered info owns them for the duration of the call
+pub(crate) struct SubmitHandles {
+    command_lists: Vec<daxa_sys::daxa_ExecutableCommandList>,
+    wait_binary_semaphores: Vec<daxa_sys::daxa_BinarySemaphore>,
+    signal_binary_semaphores: Vec<daxa_sys::daxa_BinarySemaphore>,
+    wait_timeline_semaphores: Vec<TimelinePair>,
+    signal_timeline_semaphores: Vec<TimelinePair>,
+}
+
+fn timeline_pairs(pairs: &[(TimelineSemaphore, u64)]) -> Vec<TimelinePair> {
+    pairs
+        .iter()
+        .map(|(semaphore, value)| TimelinePair {
+            semaphore: semaphore.handle(),
+            value: *value,
+        })
+        .collect()
+}
+
+impl CommandSubmitInfo<'_> {
+    pub(crate) fn handles(&self) -> SubmitHandles {
+        SubmitHandles {
+            command_lists: self
+                .command_lists
+                .iter()
+                .map(|list| list.handle())
+                .collect(),
+            wait_binary_semaphores: self
+                .wait_binary_semaphores
+                .iter()
+                .map(|semaphore| semaphore.handle())
+                .collect(),
+            signal_binary_semaphores: self
+                .signal_binary_semaphores
+                .iter()
+                .map(|semaphore| semaphore.handle())
+                .collect(),
+            wait_timeline_semaphores: timeline_pairs(self.wait_timeline_semaphores),
+            signal_timeline_semaphores: timeline_pairs(self.signal_timeline_semaphores),
+        }
+    }
+
+    pub(crate) fn as_c(&self, handles: &SubmitHandles) -> CCommandSubmitInfo {
+        CCommandSubmitInfo {
+            wait_stages: self.wait_stages,
+            command_lists: handles.command_lists.as_ptr(),
+            command_list_count: handles.command_lists.len() as u64,
+            wait_binary_semaphores: handles.wait_binary_semaphores.as_ptr(),
+            wait_binary_semaphore_count: handles.wait_binary_semaphores.len() as u64,
+            signal_binary_semaphores: handles.signal_binary_semaphores.as_ptr(),
+            signal_binary_semaphore_count: handles.signal_binary_semaphores.len() as u64,
+            wait_timeline_semaphores: handles.wait_timeline_semaphores.as_ptr(),
+            wait_timeline_semaphore_count: handles.wait_timeline_semaphores.len() as u64,
+            signal_timeline_semaphores: handles.signal_timeline_semaphores.as_ptr(),
+            signal_timeline_semaphore_count: handles.signal_timeline_semaphores.len() as u64,
+        }
+    }
 }
 
 pub struct PresentInfo<'a> {
-    wait_binary_semaphores: &'a [BinarySemaphore],
-    swapchain: Swapchain,
+    pub wait_binary_semaphores: &'a [BinarySemaphore],
+    pub swapchain: &'a Swapchain,
+}
+
+#[repr(C)]
+pub(crate) struct CPresentInfo {
+    wait_binary_semaphores: *const daxa_sys::daxa_BinarySemaphore,
+    wait_binary_semaphore_count: u64,
+    swapchain: daxa_sys::daxa_Swapchain,
+}
+
+impl PresentInfo<'_> {
+    pub(crate) fn handles(&self) -> Vec<daxa_sys::daxa_BinarySemaphore> {
+        self.wait_binary_semaphores
+            .iter()
+            .map(|semaphore| semaphore.handle())
+            .collect()
+    }
+
+    //headless swapchains are presented without daxa, so the caller resolves the daxa handle
+    pub(crate) fn as_c(
+        &self,
+        handles: &[daxa_sys::daxa_BinarySemaphore],
+        swapchain: daxa_sys::daxa_Swapchain,
+    ) -> CPresentInfo {
+        CPresentInfo {
+            wait_binary_semaphores: handles.as_ptr(),
+            wait_binary_semaphore_count: handles.len() as u64,
+            swapchain,
+        }
+    }
 }
 
 pub type BufferId = daxa_sys::daxa_BufferId;
@@ -215,100 +779,516 @@ pub type SamplerId = daxa_sys::daxa_SamplerId;
 
 pub type BufferDeviceAddress = u64;
 
-pub struct BufferInfo<'a> {
-    size: usize,
-    allocate_info: MemoryFlags,
-    name: String,
+//an info struct lowered to its daxa_sys counterpart, owning the c string its name points into
+pub(crate) struct CInfo<C> {
+    c: C,
+    _name: ffi::CString,
+}
+
+//the name has an interior nul, daxa reads names as c strings and would cut it off there
+#[derive(Debug)]
+pub(crate) struct InvalidName;
+
+impl<C> CInfo<C> {
+    pub(crate) fn new(
+        name: &str,
+        lower: impl FnOnce(daxa_sys::daxa_StringView) -> C,
+    ) -> std::result::Result<Self, InvalidName> {
+        let name = ffi::CString::new(name).map_err(|_| InvalidName)?;
+        //the string data lives on the heap, so moving the CString into Self keeps it valid
+        let c = lower(StringView::from(name.as_c_str()).as_c());
+        Ok(Self { c, _name: name })
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const C {
+        &self.c
+    }
+}
+
+pub(crate) trait AsCInfo {
+    type C;
+
+    fn as_c_info(&self) -> std::result::Result<CInfo<Self::C>, InvalidName>;
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BufferInfo {
+    pub size: usize,
+    pub allocate_info: MemoryFlags,
+    pub name: String,
+}
+
+impl AsCInfo for BufferInfo {
+    type C = daxa_sys::daxa_BufferInfo;
+
+    fn as_c_info(&self) -> std::result::Result<CInfo<Self::C>, InvalidName> {
+        CInfo::new(&self.name, |name| daxa_sys::daxa_BufferInfo {
+            size: self.size as _,
+            allocate_info: self.allocate_info.bits(),
+            name,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ImageInfo {
+    pub flags: ImageCreateFlags,
+    pub extent: Extent,
+    pub format: Format,
+    pub mip_level_count: u32,
+    pub array_layer_count: u32,
+    pub sample_count: u32,
+    pub usage: ImageUsageFlags,
+    pub allocate_info: MemoryFlags,
+    pub name: String,
+}
+
+impl Default for ImageInfo {
+    fn default() -> Self {
+        Self {
+            flags: ImageCreateFlags::empty(),
+            extent: Extent::TwoDim(0, 0),
+            format: Format::R8G8B8A8_UNORM,
+            mip_level_count: 1,
+            array_layer_count: 1,
+            sample_count: 1,
+            usage: ImageUsageFlags::empty(),
+            allocate_info: MemoryFlags::empty(),
+            name: String::new(),
+        }
+    }
 }
 
-pub struct ImageInfo<'a> {
-    flags: ImageCreateFlags,
-    extent: Extent,
-    format: Format,
-    mip_level_count: u32,
-    array_layer_count: u32,
-    sample_count: u32,
-    usage: ImageUsageFlags,
-    allocate_info: MemoryFlags,
-    name: String,
+impl AsCInfo for ImageInfo {
+    type C = daxa_sys::daxa_ImageInfo;
+
+    fn as_c_info(&self) -> std::result::Result<CInfo<Self::C>, InvalidName> {
+        let (dimensions, size) = self.extent.as_c();
+        CInfo::new(&self.name, |name| daxa_sys::daxa_ImageInfo {
+            flags: self.flags.bits(),
+            dimensions,
+            format: self.format as _,
+            size,
+            mip_level_count: self.mip_level_count,
+            array_layer_count: self.array_layer_count,
+            sample_count: self.sample_count,
+            usage: self.usage.bits(),
+            allocate_info: self.allocate_info.bits(),
+            name,
+        })
+    }
 }
 
+#[derive(Clone, Debug)]
 pub struct ImageViewInfo {
-    ty: ImageViewType,
-    format: Format,
-    image: ImageId,
-    slice: ImageMipArraySlice,
-    name: String,
+    pub ty: ImageViewType,
+    pub format: Format,
+    pub image: ImageId,
+    pub slice: ImageMipArraySlice,
+    pub name: String,
+}
+
+impl AsCInfo for ImageViewInfo {
+    type C = daxa_sys::daxa_ImageViewInfo;
+
+    fn as_c_info(&self) -> std::result::Result<CInfo<Self::C>, InvalidName> {
+        CInfo::new(&self.name, |name| daxa_sys::daxa_ImageViewInfo {
+            type_: self.ty.bits() as _,
+            format: self.format as _,
+            image: self.image,
+            slice: unsafe { mem::transmute(self.slice) },
+            name,
+        })
+    }
 }
 
+#[derive(Clone, Debug)]
 pub struct SamplerInfo {
-    magnification_filter: Filter,
-    minification_filter: Filter,
-    mipmap_filter: Filter,
-    reduction_mode: ReductionMode,
-    address_mode_u: SamplerAddressMode,
-    address_mode_v: SamplerAddressMode,
-    address_mode_w: SamplerAddressMode,
-    mip_lod_bias: f32,
-    enable_anisotropy: bool,
-    max_anisotropy: f32,
-    compare_op: CompareOp,
-    min_lod: f32,
-    max_lod: f32,
-    border_color: BorderColor,
-    enable_unnormalized_coordinates: bool,
-    name: String,
+    pub magnification_filter: Filter,
+    pub minification_filter: Filter,
+    pub mipmap_filter: Filter,
+    pub reduction_mode: ReductionMode,
+    pub address_mode_u: SamplerAddressMode,
+    pub address_mode_v: SamplerAddressMode,
+    pub address_mode_w: SamplerAddressMode,
+    pub mip_lod_bias: f32,
+    pub enable_anisotropy: bool,
+    pub max_anisotropy: f32,
+    pub enable_compare: bool,
+    pub compare_op: CompareOp,
+    pub min_lod: f32,
+    pub max_lod: f32,
+    pub border_color: BorderColor,
+    pub enable_unnormalized_coordinates: bool,
+    pub name: String,
 }
 
+impl Default for SamplerInfo {
+    fn default() -> Self {
+        Self {
+            magnification_filter: Filter::LINEAR,
+            minification_filter: Filter::LINEAR,
+            mipmap_filter: Filter::LINEAR,
+            reduction_mode: ReductionMode::WeightedAverage,
+            address_mode_u: SamplerAddressMode::ClampToEdge,
+            address_mode_v: SamplerAddressMode::ClampToEdge,
+            address_mode_w: SamplerAddressMode::ClampToEdge,
+            mip_lod_bias: 0.0,
+            enable_anisotropy: false,
+            max_anisotropy: 0.0,
+            enable_compare: false,
+            compare_op: CompareOp::Always,
+            min_lod: 0.0,
+            max_lod: 1000.0,
+            border_color: BorderColor::IntTransparentBlack,
+            enable_unnormalized_coordinates: false,
+            name: String::new(),
+        }
+    }
+}
+
+impl AsCInfo for SamplerInfo {
+    type C = daxa_sys::daxa_SamplerInfo;
+
+    fn as_c_info(&self) -> std::result::Result<CInfo<Self::C>, InvalidName> {
+        CInfo::new(&self.name, |name| daxa_sys::daxa_SamplerInfo {
+            magnification_filter: self.magnification_filter.bits() as _,
+            minification_filter: self.minification_filter.bits() as _,
+            mipmap_filter: self.mipmap_filter.bits() as _,
+            reduction_mode: self.reduction_mode as _,
+            address_mode_u: self.address_mode_u as _,
+            address_mode_v: self.address_mode_v as _,
+            address_mode_w: self.address_mode_w as _,
+            mip_lod_bias: self.mip_lod_bias,
+            enable_anisotropy: self.enable_anisotropy as _,
+            max_anisotropy: self.max_anisotropy,
+            enable_compare: self.enable_compare as _,
+            compare_op: self.compare_op as _,
+            min_lod: self.min_lod,
+            max_lod: self.max_lod,
+            border_color: self.border_color as _,
+            enable_unnormalized_coordinates: self.enable_unnormalized_coordinates as _,
+            name,
+        })
+    }
+}
+
+//keeps its device alive and queues the resource for destruction once the last clone drops
+struct ResourceHandle<Id: Copy, Info> {
+    device: Device,
+    id: Id,
+    info: Info,
+    zombie: fn(Id) -> Zombie,
+}
+
+impl<Id: Copy, Info> Drop for ResourceHandle<Id, Info> {
+    fn drop(&mut self) {
+        self.device.zombify((self.zombie)(self.id));
+    }
+}
+
+struct BufferState {
+    info: BufferInfo,
+    //shared by all clones so only one mapping can be alive at a time
+    mapped: sync::atomic::AtomicBool,
+}
+
+//cloning shares ownership like an Arc, it never duplicates the gpu resource
 #[derive(Clone)]
 pub struct Buffer {
-    device: Device,
-    handle: BufferId,
+    handle: sync::Arc<ResourceHandle<BufferId, BufferState>>,
 }
 
 impl Buffer {
+    pub(crate) fn from_raw(device: Device, id: BufferId, info: BufferInfo) -> Self {
+        Self {
+            handle: sync::Arc::new(ResourceHandle {
+                device,
+                id,
+                info: BufferState {
+                    info,
+                    mapped: sync::atomic::AtomicBool::new(false),
+                },
+                zombie: Zombie::Buffer,
+            }),
+        }
+    }
+
     pub fn id(&self) -> BufferId {
-        unsafe { self.handle }
+        self.handle.id
+    }
+
+    pub fn device(&self) -> &Device {
+        &self.handle.device
+    }
+
+    pub fn info(&self) -> &BufferInfo {
+        &self.handle.info.info
+    }
+
+    //read and write access, only for buffers created with HOST_ACCESS_RANDOM
+    pub fn map<T: Pod>(&self) -> std::result::Result<BufferMapping<'_, T>, MapError> {
+        let flags = self.info().allocate_info;
+        if !flags.contains(MemoryFlags::HOST_ACCESS_RANDOM) {
+            if flags.contains(MemoryFlags::SEQUENTIAL_WRITE) {
+                return Err(MapError::WriteOnly);
+            }
+            return Err(MapError::NotHostVisible);
+        }
+        let (ptr, len) = self.map_raw::<T>()?;
+        Ok(BufferMapping {
+            buffer: self,
+            ptr,
+            len,
+        })
+    }
+
+    //write access for any host visible buffer, reading back SEQUENTIAL_WRITE memory is uncached and slow
+    pub fn map_write<T: Pod>(&self) -> std::result::Result<WriteMapping<'_, T>, MapError> {
+        let (ptr, len) = self.map_raw::<T>()?;
+        Ok(WriteMapping {
+            buffer: self,
+            ptr,
+            len,
+        })
+    }
+
+    fn map_raw<T: Pod>(&self) -> std::result::Result<(*mut T, usize), MapError> {
+        let info = self.info();
+        if !info
+            .allocate_info
+            .intersects(MemoryFlags::HOST_ACCESS_RANDOM | MemoryFlags::SEQUENTIAL_WRITE)
+        {
+            return Err(MapError::NotHostVisible);
+        }
+        let element_size = mem::size_of::<T>();
+        if element_size == 0 || info.size % element_size != 0 {
+            return Err(MapError::SizeMismatch {
+                buffer_size: info.size,
+                element_size,
+            });
+        }
+        let address = self
+            .device()
+            .buffer_host_address(self.id())
+            .map_err(MapError::Daxa)?;
+        if address.is_null() {
+            return Err(MapError::NotHostVisible);
+        }
+        let alignment = mem::align_of::<T>();
+        if address as usize % alignment != 0 {
+            return Err(MapError::Misaligned {
+                address: address as usize,
+                alignment,
+            });
+        }
+        if self
+            .handle
+            .info
+            .mapped
+            .swap(true, sync::atomic::Ordering::Acquire)
+        {
+            return Err(MapError::AlreadyMapped);
+        }
+        Ok((address.cast(), info.size / element_size))
+    }
+
+    fn unmap(&self) {
+        self.handle
+            .info
+            .mapped
+            .store(false, sync::atomic::Ordering::Release);
+    }
+}
+
+#[derive(Debug)]
+pub enum MapError {
+    NotHostVisible,
+    WriteOnly,
+    AlreadyMapped,
+    SizeMismatch {
+        buffer_size: usize,
+        element_size: usize,
+    },
+    Misaligned {
+        address: usize,
+        alignment: usize,
+    },
+    Daxa(DaxaError),
+}
+
+impl std::fmt::Display for MapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &self)
+    }
+}
+impl std::error::Error for MapError {}
+
+//borrows the buffer, so the memory stays alive and mapped while the slice is in use
+pub struct BufferMapping<'a, T: Pod> {
+    buffer: &'a Buffer,
+    ptr: *mut T,
+    len: usize,
+}
+
+impl<T: Pod> ops::Deref for BufferMapping<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl<T: Pod> ops::DerefMut for BufferMapping<'_, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl<T: Pod> Drop for BufferMapping<'_, T> {
+    fn drop(&mut self) {
+        self.buffer.unmap();
+    }
+}
+
+//no deref on purpose, handing out a slice would allow reads from write combined memory
+pub struct WriteMapping<'a, T: Pod> {
+    buffer: &'a Buffer,
+    ptr: *mut T,
+    len: usize,
+}
+
+impl<T: Pod> WriteMapping<'_, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn write(&mut self, index: usize, value: T) {
+        assert!(
+            index < self.len,
+            "write index {} out of bounds for mapping of length {}",
+            index,
+            self.len
+        );
+        unsafe { self.ptr.add(index).write(value) }
+    }
+
+    pub fn write_slice(&mut self, offset: usize, values: &[T]) {
+        assert!(
+            offset <= self.len && values.len() <= self.len - offset,
+            "write range {}..{} out of bounds for mapping of length {}",
+            offset,
+            offset + values.len(),
+            self.len
+        );
+        unsafe {
+            std::ptr::copy_nonoverlapping(values.as_ptr(), self.ptr.add(offset), values.len())
+        }
+    }
+}
+
+impl<T: Pod> Drop for WriteMapping<'_, T> {
+    fn drop(&mut self) {
+        self.buffer.unmap();
     }
 }
 
 #[derive(Clone)]
-struct Image {
-    device: Device,
-    handle: ImageId,
+pub struct Image {
+    handle: sync::Arc<ResourceHandle<ImageId, ImageInfo>>,
 }
 
 impl Image {
+    pub(crate) fn from_raw(device: Device, id: ImageId, info: ImageInfo) -> Self {
+        Self {
+            handle: sync::Arc::new(ResourceHandle {
+                device,
+                id,
+                info,
+                zombie: Zombie::Image,
+            }),
+        }
+    }
+
     pub fn id(&self) -> ImageId {
-        self.handle
+        self.handle.id
+    }
+
+    pub fn device(&self) -> &Device {
+        &self.handle.device
+    }
+
+    pub fn info(&self) -> &ImageInfo {
+        &self.handle.info
     }
 }
 
 #[derive(Clone)]
-struct ImageView {
-    device: Device,
-    handle: ImageViewId,
+pub struct ImageView {
+    handle: sync::Arc<ResourceHandle<ImageViewId, ImageViewInfo>>,
 }
 
 impl ImageView {
-    fn id(&self) -> ImageViewId {
-        self.handle
+    pub(crate) fn from_raw(device: Device, id: ImageViewId, info: ImageViewInfo) -> Self {
+        Self {
+            handle: sync::Arc::new(ResourceHandle {
+                device,
+                id,
+                info,
+                zombie: Zombie::ImageView,
+            }),
+        }
+    }
+
+    pub fn id(&self) -> ImageViewId {
+        self.handle.id
+    }
+
+    pub fn device(&self) -> &Device {
+        &self.handle.device
+    }
+
+    pub fn info(&self) -> &ImageViewInfo {
+        &self.handle.info
     }
 }
 
 #[derive(Clone)]
 pub struct Sampler {
-    device: Device,
-    handle: SamplerId,
+    handle: sync::Arc<ResourceHandle<SamplerId, SamplerInfo>>,
 }
 
 impl Sampler {
-    fn id(&self) -> SamplerId {
-        self.handle
+    pub(crate) fn from_raw(device: Device, id: SamplerId, info: SamplerInfo) -> Self {
+        Self {
+            handle: sync::Arc::new(ResourceHandle {
+                device,
+                id,
+                info,
+                zombie: Zombie::Sampler,
+            }),
+        }
+    }
+
+    pub fn id(&self) -> SamplerId {
+        self.handle.id
+    }
+
+    pub fn device(&self) -> &Device {
+        &self.handle.device
+    }
+
+    pub fn info(&self) -> &SamplerInfo {
+        &self.handle.info
     }
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CompareOp {
     Never = daxa_sys::VkCompareOp_VK_COMPARE_OP_NEVER,
     Less = daxa_sys::VkCompareOp_VK_COMPARE_OP_LESS,
@@ -336,17 +1316,19 @@ bitflags! {
 
 pub type MemoryBlock = daxa_sys::daxa_MemoryBlock;
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
 pub struct MemoryRequirements {
-    size: DeviceSize,
-    alignment: DeviceSize,
-    memory_type_bits: u32,
+    pub size: DeviceSize,
+    pub alignment: DeviceSize,
+    pub memory_type_bits: u32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
 pub struct MemoryBlockInfo {
-    requirements: daxa_sys::VkMemoryRequirements,
-    flags: MemoryFlags,
+    pub requirements: MemoryRequirements,
+    pub flags: MemoryFlags,
 }
 
 #[repr(C)]
@@ -370,45 +1352,117 @@ pub struct MemoryAllocateInfo {
     info: AllocInfo,
 }
 
-#[repr(C)]
+#[derive(Clone, Debug, Default)]
+pub struct TimelineQueryPoolInfo {
+    pub query_count: u32,
+    pub name: String,
+}
+
+impl AsCInfo for TimelineQueryPoolInfo {
+    type C = daxa_sys::daxa_TimelineQueryPoolInfo;
+
+    fn as_c_info(&self) -> std::result::Result<CInfo<Self::C>, InvalidName> {
+        CInfo::new(&self.name, |name| daxa_sys::daxa_TimelineQueryPoolInfo {
+            query_count: self.query_count,
+            name,
+        })
+    }
+}
+
+struct TimelineQueryPoolHandle {
+    handle: daxa_sys::daxa_TimelineQueryPool,
+    info: TimelineQueryPoolInfo,
+    _device: Device,
+}
+
+impl Drop for TimelineQueryPoolHandle {
+    fn drop(&mut self) {
+        unsafe {
+            daxa_sys::daxa_timeline_query_pool_dec_refcnt(self.handle);
+        }
+    }
+}
+
 #[derive(Clone)]
-pub struct AllocateInfo {
-    query_count: u32,
-    name: String,
+pub struct TimelineQueryPool {
+    inner: sync::Arc<TimelineQueryPoolHandle>,
+}
+
+impl TimelineQueryPool {
+    pub(crate) fn from_raw(
+        device: Device,
+        handle: daxa_sys::daxa_TimelineQueryPool,
+        info: TimelineQueryPoolInfo,
+    ) -> Self {
+        Self {
+            inner: sync::Arc::new(TimelineQueryPoolHandle {
+                handle,
+                info,
+                _device: device,
+            }),
+        }
+    }
+
+    pub fn info(&self) -> &TimelineQueryPoolInfo {
+        &self.inner.info
+    }
 }
 
+//data is only initialized while has_value is set, so None needs no value of T
 #[repr(C)]
-#[derive(Clone, Copy)]
 pub struct Option<T> {
-    data: T,
+    data: mem::MaybeUninit<T>,
     has_value: bool,
 }
 
-impl<T: 'static> From<std::option::Option<T>> for Option<T> {
+impl<T: Copy> Clone for Option<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Copy> Copy for Option<T> {}
+
+impl<T> From<std::option::Option<T>> for Option<T> {
     fn from(value: std::option::Option<T>) -> Self {
-        let Some(data) = value else {
-            return Option {
-                data: unsafe { mem::MaybeUninit::uninit().assume_init() },
-                has_value: false,
-            }
-        };
+        match value {
+            Some(data) => Option::some(data),
+            None => Option::default(),
+        }
+    }
+}
+
+impl<T> Default for Option<T> {
+    //zeroed rather than uninit, daxa copies the whole struct even when it is empty
+    fn default() -> Self {
+        Option {
+            data: mem::MaybeUninit::zeroed(),
+            has_value: false,
+        }
+    }
+}
 
+impl<T> Option<T> {
+    pub(crate) fn some(data: T) -> Self {
         Option {
-            data,
+            data: mem::MaybeUninit::new(data),
             has_value: true,
         }
     }
+
+    pub(crate) fn as_ref(&self) -> std::option::Option<&T> {
+        self.has_value
+            .then(|| unsafe { self.data.assume_init_ref() })
+    }
 }
 
-impl<T: 'static> Into<std::option::Option<T>> for Option<T> {
+impl<T> Into<std::option::Option<T>> for Option<T> {
     fn into(self) -> std::option::Option<T> {
-        let Option { has_value: true, .. } = &self else {
-            return None;
-        };
-        Some(self.data)
+        self.has_value.then(|| unsafe { self.data.assume_init() })
     }
 }
 
+#[repr(C)]
 pub struct StringView<'a> {
     ptr: *const os::raw::c_char,
     len: usize,
@@ -441,6 +1495,29 @@ impl<'a> StringView<'a> {
     }
 }
 
+impl<'a> StringView<'a> {
+    pub(crate) fn as_c(&self) -> daxa_sys::daxa_StringView {
+        daxa_sys::daxa_StringView {
+            data: self.ptr,
+            size: self.len as _,
+        }
+    }
+
+    pub(crate) fn as_bytes(&self) -> &'a [u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr.cast(), self.len) }
+    }
+}
+
+impl<'a> From<&'a ffi::CStr> for StringView<'a> {
+    fn from(data: &'a ffi::CStr) -> Self {
+        Self {
+            ptr: data.as_ptr(),
+            len: data.to_bytes().len(),
+            marker: PhantomData,
+        }
+    }
+}
+
 impl<'a, const N: usize> From<&'a [u8; N]> for StringView<'a> {
     fn from(data: &'a [u8; N]) -> Self {
         let ptr = data as *const u8 as *const os::raw::c_char;
@@ -453,6 +1530,7 @@ impl<'a, const N: usize> From<&'a [u8; N]> for StringView<'a> {
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Format {
     UNDEFINED = daxa_sys::VkFormat_VK_FORMAT_UNDEFINED,
     R4G4_UNORM_PACK8 = daxa_sys::VkFormat_VK_FORMAT_R4G4_UNORM_PACK8,
@@ -799,19 +1877,408 @@ pub enum Format {
     A4B4G4R4_UNORM_PACK16_EXT = daxa_sys::VkFormat_VK_FORMAT_A4B4G4R4_UNORM_PACK16_EXT,
 }
 
+impl TryFrom<u32> for Format {
+    type Error = u32;
+
+    //surfaces may report formats newer than these bindings, so never transmute a raw format
+    fn try_from(format: u32) -> std::result::Result<Self, u32> {
+        use Format::*;
+        Ok(match format {
+            daxa_sys::VkFormat_VK_FORMAT_UNDEFINED => UNDEFINED,
+            daxa_sys::VkFormat_VK_FORMAT_R4G4_UNORM_PACK8 => R4G4_UNORM_PACK8,
+            daxa_sys::VkFormat_VK_FORMAT_R4G4B4A4_UNORM_PACK16 => R4G4B4A4_UNORM_PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_B4G4R4A4_UNORM_PACK16 => B4G4R4A4_UNORM_PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_R5G6B5_UNORM_PACK16 => R5G6B5_UNORM_PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_B5G6R5_UNORM_PACK16 => B5G6R5_UNORM_PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_R5G5B5A1_UNORM_PACK16 => R5G5B5A1_UNORM_PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_B5G5R5A1_UNORM_PACK16 => B5G5R5A1_UNORM_PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_A1R5G5B5_UNORM_PACK16 => A1R5G5B5_UNORM_PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_R8_UNORM => R8_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R8_SNORM => R8_SNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R8_USCALED => R8_USCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R8_SSCALED => R8_SSCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R8_UINT => R8_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R8_SINT => R8_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R8_SRGB => R8_SRGB,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8_UNORM => R8G8_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8_SNORM => R8G8_SNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8_USCALED => R8G8_USCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8_SSCALED => R8G8_SSCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8_UINT => R8G8_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8_SINT => R8G8_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8_SRGB => R8G8_SRGB,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8_UNORM => R8G8B8_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8_SNORM => R8G8B8_SNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8_USCALED => R8G8B8_USCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8_SSCALED => R8G8B8_SSCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8_UINT => R8G8B8_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8_SINT => R8G8B8_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8_SRGB => R8G8B8_SRGB,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8_UNORM => B8G8R8_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8_SNORM => B8G8R8_SNORM,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8_USCALED => B8G8R8_USCALED,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8_SSCALED => B8G8R8_SSCALED,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8_UINT => B8G8R8_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8_SINT => B8G8R8_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8_SRGB => B8G8R8_SRGB,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8A8_UNORM => R8G8B8A8_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8A8_SNORM => R8G8B8A8_SNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8A8_USCALED => R8G8B8A8_USCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8A8_SSCALED => R8G8B8A8_SSCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8A8_UINT => R8G8B8A8_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8A8_SINT => R8G8B8A8_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R8G8B8A8_SRGB => R8G8B8A8_SRGB,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8A8_UNORM => B8G8R8A8_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8A8_SNORM => B8G8R8A8_SNORM,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8A8_USCALED => B8G8R8A8_USCALED,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8A8_SSCALED => B8G8R8A8_SSCALED,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8A8_UINT => B8G8R8A8_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8A8_SINT => B8G8R8A8_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8A8_SRGB => B8G8R8A8_SRGB,
+            daxa_sys::VkFormat_VK_FORMAT_A8B8G8R8_UNORM_PACK32 => A8B8G8R8_UNORM_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A8B8G8R8_SNORM_PACK32 => A8B8G8R8_SNORM_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A8B8G8R8_USCALED_PACK32 => A8B8G8R8_USCALED_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A8B8G8R8_SSCALED_PACK32 => A8B8G8R8_SSCALED_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A8B8G8R8_UINT_PACK32 => A8B8G8R8_UINT_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A8B8G8R8_SINT_PACK32 => A8B8G8R8_SINT_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A8B8G8R8_SRGB_PACK32 => A8B8G8R8_SRGB_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A2R10G10B10_UNORM_PACK32 => A2R10G10B10_UNORM_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A2R10G10B10_SNORM_PACK32 => A2R10G10B10_SNORM_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A2R10G10B10_USCALED_PACK32 => A2R10G10B10_USCALED_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A2R10G10B10_SSCALED_PACK32 => A2R10G10B10_SSCALED_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A2R10G10B10_UINT_PACK32 => A2R10G10B10_UINT_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A2R10G10B10_SINT_PACK32 => A2R10G10B10_SINT_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A2B10G10R10_UNORM_PACK32 => A2B10G10R10_UNORM_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A2B10G10R10_SNORM_PACK32 => A2B10G10R10_SNORM_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A2B10G10R10_USCALED_PACK32 => A2B10G10R10_USCALED_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A2B10G10R10_SSCALED_PACK32 => A2B10G10R10_SSCALED_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A2B10G10R10_UINT_PACK32 => A2B10G10R10_UINT_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_A2B10G10R10_SINT_PACK32 => A2B10G10R10_SINT_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_R16_UNORM => R16_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R16_SNORM => R16_SNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R16_USCALED => R16_USCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R16_SSCALED => R16_SSCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R16_UINT => R16_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R16_SINT => R16_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R16_SFLOAT => R16_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16_UNORM => R16G16_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16_SNORM => R16G16_SNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16_USCALED => R16G16_USCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16_SSCALED => R16G16_SSCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16_UINT => R16G16_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16_SINT => R16G16_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16_SFLOAT => R16G16_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16_UNORM => R16G16B16_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16_SNORM => R16G16B16_SNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16_USCALED => R16G16B16_USCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16_SSCALED => R16G16B16_SSCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16_UINT => R16G16B16_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16_SINT => R16G16B16_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16_SFLOAT => R16G16B16_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16A16_UNORM => R16G16B16A16_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16A16_SNORM => R16G16B16A16_SNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16A16_USCALED => R16G16B16A16_USCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16A16_SSCALED => R16G16B16A16_SSCALED,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16A16_UINT => R16G16B16A16_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16A16_SINT => R16G16B16A16_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16B16A16_SFLOAT => R16G16B16A16_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_R32_UINT => R32_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R32_SINT => R32_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R32_SFLOAT => R32_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_R32G32_UINT => R32G32_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R32G32_SINT => R32G32_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R32G32_SFLOAT => R32G32_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_R32G32B32_UINT => R32G32B32_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R32G32B32_SINT => R32G32B32_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R32G32B32_SFLOAT => R32G32B32_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_R32G32B32A32_UINT => R32G32B32A32_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R32G32B32A32_SINT => R32G32B32A32_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R32G32B32A32_SFLOAT => R32G32B32A32_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_R64_UINT => R64_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R64_SINT => R64_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R64_SFLOAT => R64_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_R64G64_UINT => R64G64_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R64G64_SINT => R64G64_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R64G64_SFLOAT => R64G64_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_R64G64B64_UINT => R64G64B64_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R64G64B64_SINT => R64G64B64_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R64G64B64_SFLOAT => R64G64B64_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_R64G64B64A64_UINT => R64G64B64A64_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_R64G64B64A64_SINT => R64G64B64A64_SINT,
+            daxa_sys::VkFormat_VK_FORMAT_R64G64B64A64_SFLOAT => R64G64B64A64_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_B10G11R11_UFLOAT_PACK32 => B10G11R11_UFLOAT_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_E5B9G9R9_UFLOAT_PACK32 => E5B9G9R9_UFLOAT_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_D16_UNORM => D16_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_X8_D24_UNORM_PACK32 => X8_D24_UNORM_PACK32,
+            daxa_sys::VkFormat_VK_FORMAT_D32_SFLOAT => D32_SFLOAT,
+            daxa_sys::VkFormat_VK_FORMAT_S8_UINT => S8_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_D16_UNORM_S8_UINT => D16_UNORM_S8_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_D24_UNORM_S8_UINT => D24_UNORM_S8_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_D32_SFLOAT_S8_UINT => D32_SFLOAT_S8_UINT,
+            daxa_sys::VkFormat_VK_FORMAT_BC1_RGB_UNORM_BLOCK => BC1_RGB_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC1_RGB_SRGB_BLOCK => BC1_RGB_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC1_RGBA_UNORM_BLOCK => BC1_RGBA_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC1_RGBA_SRGB_BLOCK => BC1_RGBA_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC2_UNORM_BLOCK => BC2_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC2_SRGB_BLOCK => BC2_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC3_UNORM_BLOCK => BC3_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC3_SRGB_BLOCK => BC3_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC4_UNORM_BLOCK => BC4_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC4_SNORM_BLOCK => BC4_SNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC5_UNORM_BLOCK => BC5_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC5_SNORM_BLOCK => BC5_SNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC6H_UFLOAT_BLOCK => BC6H_UFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC6H_SFLOAT_BLOCK => BC6H_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC7_UNORM_BLOCK => BC7_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_BC7_SRGB_BLOCK => BC7_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ETC2_R8G8B8_UNORM_BLOCK => ETC2_R8G8B8_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ETC2_R8G8B8_SRGB_BLOCK => ETC2_R8G8B8_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ETC2_R8G8B8A1_UNORM_BLOCK => ETC2_R8G8B8A1_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ETC2_R8G8B8A1_SRGB_BLOCK => ETC2_R8G8B8A1_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ETC2_R8G8B8A8_UNORM_BLOCK => ETC2_R8G8B8A8_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ETC2_R8G8B8A8_SRGB_BLOCK => ETC2_R8G8B8A8_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_EAC_R11_UNORM_BLOCK => EAC_R11_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_EAC_R11_SNORM_BLOCK => EAC_R11_SNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_EAC_R11G11_UNORM_BLOCK => EAC_R11G11_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_EAC_R11G11_SNORM_BLOCK => EAC_R11G11_SNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_4x4_UNORM_BLOCK => ASTC_4x4_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_4x4_SRGB_BLOCK => ASTC_4x4_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_5x4_UNORM_BLOCK => ASTC_5x4_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_5x4_SRGB_BLOCK => ASTC_5x4_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_5x5_UNORM_BLOCK => ASTC_5x5_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_5x5_SRGB_BLOCK => ASTC_5x5_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_6x5_UNORM_BLOCK => ASTC_6x5_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_6x5_SRGB_BLOCK => ASTC_6x5_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_6x6_UNORM_BLOCK => ASTC_6x6_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_6x6_SRGB_BLOCK => ASTC_6x6_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_8x5_UNORM_BLOCK => ASTC_8x5_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_8x5_SRGB_BLOCK => ASTC_8x5_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_8x6_UNORM_BLOCK => ASTC_8x6_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_8x6_SRGB_BLOCK => ASTC_8x6_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_8x8_UNORM_BLOCK => ASTC_8x8_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_8x8_SRGB_BLOCK => ASTC_8x8_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_10x5_UNORM_BLOCK => ASTC_10x5_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_10x5_SRGB_BLOCK => ASTC_10x5_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_10x6_UNORM_BLOCK => ASTC_10x6_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_10x6_SRGB_BLOCK => ASTC_10x6_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_10x8_UNORM_BLOCK => ASTC_10x8_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_10x8_SRGB_BLOCK => ASTC_10x8_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_10x10_UNORM_BLOCK => ASTC_10x10_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_10x10_SRGB_BLOCK => ASTC_10x10_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_12x10_UNORM_BLOCK => ASTC_12x10_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_12x10_SRGB_BLOCK => ASTC_12x10_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_12x12_UNORM_BLOCK => ASTC_12x12_UNORM_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_12x12_SRGB_BLOCK => ASTC_12x12_SRGB_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_G8B8G8R8_422_UNORM => G8B8G8R8_422_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_B8G8R8G8_422_UNORM => B8G8R8G8_422_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_G8_B8_R8_3PLANE_420_UNORM => G8_B8_R8_3PLANE_420_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_G8_B8R8_2PLANE_420_UNORM => G8_B8R8_2PLANE_420_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_G8_B8_R8_3PLANE_422_UNORM => G8_B8_R8_3PLANE_422_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_G8_B8R8_2PLANE_422_UNORM => G8_B8R8_2PLANE_422_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_G8_B8_R8_3PLANE_444_UNORM => G8_B8_R8_3PLANE_444_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_R10X6_UNORM_PACK16 => R10X6_UNORM_PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_R10X6G10X6_UNORM_2PACK16 => R10X6G10X6_UNORM_2PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_R10X6G10X6B10X6A10X6_UNORM_4PACK16 => {
+                R10X6G10X6B10X6A10X6_UNORM_4PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G10X6B10X6G10X6R10X6_422_UNORM_4PACK16 => {
+                G10X6B10X6G10X6R10X6_422_UNORM_4PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_B10X6G10X6R10X6G10X6_422_UNORM_4PACK16 => {
+                B10X6G10X6R10X6G10X6_422_UNORM_4PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G10X6_B10X6_R10X6_3PLANE_420_UNORM_3PACK16 => {
+                G10X6_B10X6_R10X6_3PLANE_420_UNORM_3PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16 => {
+                G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G10X6_B10X6_R10X6_3PLANE_422_UNORM_3PACK16 => {
+                G10X6_B10X6_R10X6_3PLANE_422_UNORM_3PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G10X6_B10X6R10X6_2PLANE_422_UNORM_3PACK16 => {
+                G10X6_B10X6R10X6_2PLANE_422_UNORM_3PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G10X6_B10X6_R10X6_3PLANE_444_UNORM_3PACK16 => {
+                G10X6_B10X6_R10X6_3PLANE_444_UNORM_3PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_R12X4_UNORM_PACK16 => R12X4_UNORM_PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_R12X4G12X4_UNORM_2PACK16 => R12X4G12X4_UNORM_2PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_R12X4G12X4B12X4A12X4_UNORM_4PACK16 => {
+                R12X4G12X4B12X4A12X4_UNORM_4PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G12X4B12X4G12X4R12X4_422_UNORM_4PACK16 => {
+                G12X4B12X4G12X4R12X4_422_UNORM_4PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_B12X4G12X4R12X4G12X4_422_UNORM_4PACK16 => {
+                B12X4G12X4R12X4G12X4_422_UNORM_4PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G12X4_B12X4_R12X4_3PLANE_420_UNORM_3PACK16 => {
+                G12X4_B12X4_R12X4_3PLANE_420_UNORM_3PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G12X4_B12X4R12X4_2PLANE_420_UNORM_3PACK16 => {
+                G12X4_B12X4R12X4_2PLANE_420_UNORM_3PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G12X4_B12X4_R12X4_3PLANE_422_UNORM_3PACK16 => {
+                G12X4_B12X4_R12X4_3PLANE_422_UNORM_3PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G12X4_B12X4R12X4_2PLANE_422_UNORM_3PACK16 => {
+                G12X4_B12X4R12X4_2PLANE_422_UNORM_3PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G12X4_B12X4_R12X4_3PLANE_444_UNORM_3PACK16 => {
+                G12X4_B12X4_R12X4_3PLANE_444_UNORM_3PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G16B16G16R16_422_UNORM => G16B16G16R16_422_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_B16G16R16G16_422_UNORM => B16G16R16G16_422_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_G16_B16_R16_3PLANE_420_UNORM => {
+                G16_B16_R16_3PLANE_420_UNORM
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G16_B16R16_2PLANE_420_UNORM => G16_B16R16_2PLANE_420_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_G16_B16_R16_3PLANE_422_UNORM => {
+                G16_B16_R16_3PLANE_422_UNORM
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G16_B16R16_2PLANE_422_UNORM => G16_B16R16_2PLANE_422_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_G16_B16_R16_3PLANE_444_UNORM => {
+                G16_B16_R16_3PLANE_444_UNORM
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G8_B8R8_2PLANE_444_UNORM => G8_B8R8_2PLANE_444_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_G10X6_B10X6R10X6_2PLANE_444_UNORM_3PACK16 => {
+                G10X6_B10X6R10X6_2PLANE_444_UNORM_3PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G12X4_B12X4R12X4_2PLANE_444_UNORM_3PACK16 => {
+                G12X4_B12X4R12X4_2PLANE_444_UNORM_3PACK16
+            }
+            daxa_sys::VkFormat_VK_FORMAT_G16_B16R16_2PLANE_444_UNORM => G16_B16R16_2PLANE_444_UNORM,
+            daxa_sys::VkFormat_VK_FORMAT_A4R4G4B4_UNORM_PACK16 => A4R4G4B4_UNORM_PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_A4B4G4R4_UNORM_PACK16 => A4B4G4R4_UNORM_PACK16,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_4x4_SFLOAT_BLOCK => ASTC_4x4_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_5x4_SFLOAT_BLOCK => ASTC_5x4_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_5x5_SFLOAT_BLOCK => ASTC_5x5_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_6x5_SFLOAT_BLOCK => ASTC_6x5_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_6x6_SFLOAT_BLOCK => ASTC_6x6_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_8x5_SFLOAT_BLOCK => ASTC_8x5_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_8x6_SFLOAT_BLOCK => ASTC_8x6_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_8x8_SFLOAT_BLOCK => ASTC_8x8_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_10x5_SFLOAT_BLOCK => ASTC_10x5_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_10x6_SFLOAT_BLOCK => ASTC_10x6_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_10x8_SFLOAT_BLOCK => ASTC_10x8_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_10x10_SFLOAT_BLOCK => ASTC_10x10_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_12x10_SFLOAT_BLOCK => ASTC_12x10_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_ASTC_12x12_SFLOAT_BLOCK => ASTC_12x12_SFLOAT_BLOCK,
+            daxa_sys::VkFormat_VK_FORMAT_PVRTC1_2BPP_UNORM_BLOCK_IMG => PVRTC1_2BPP_UNORM_BLOCK_IMG,
+            daxa_sys::VkFormat_VK_FORMAT_PVRTC1_4BPP_UNORM_BLOCK_IMG => PVRTC1_4BPP_UNORM_BLOCK_IMG,
+            daxa_sys::VkFormat_VK_FORMAT_PVRTC2_2BPP_UNORM_BLOCK_IMG => PVRTC2_2BPP_UNORM_BLOCK_IMG,
+            daxa_sys::VkFormat_VK_FORMAT_PVRTC2_4BPP_UNORM_BLOCK_IMG => PVRTC2_4BPP_UNORM_BLOCK_IMG,
+            daxa_sys::VkFormat_VK_FORMAT_PVRTC1_2BPP_SRGB_BLOCK_IMG => PVRTC1_2BPP_SRGB_BLOCK_IMG,
+            daxa_sys::VkFormat_VK_FORMAT_PVRTC1_4BPP_SRGB_BLOCK_IMG => PVRTC1_4BPP_SRGB_BLOCK_IMG,
+            daxa_sys::VkFormat_VK_FORMAT_PVRTC2_2BPP_SRGB_BLOCK_IMG => PVRTC2_2BPP_SRGB_BLOCK_IMG,
+            daxa_sys::VkFormat_VK_FORMAT_PVRTC2_4BPP_SRGB_BLOCK_IMG => PVRTC2_4BPP_SRGB_BLOCK_IMG,
+            daxa_sys::VkFormat_VK_FORMAT_R16G16_S10_5_NV => R16G16_S10_5_NV,
+            daxa_sys::VkFormat_VK_FORMAT_A1B5G5R5_UNORM_PACK16_KHR => A1B5G5R5_UNORM_PACK16_KHR,
+            daxa_sys::VkFormat_VK_FORMAT_A8_UNORM_KHR => A8_UNORM_KHR,
+            _ => return Err(format),
+        })
+    }
+}
+
 pub const VK_UUID_SIZE: usize = 16;
 
 pub type VkPhysicalDeviceLimits = daxa_sys::VkPhysicalDeviceLimits;
 pub type VkPhysicalDeviceSparseProperties = daxa_sys::VkPhysicalDeviceSparseProperties;
 
-pub struct VkPhysicalDeviceProperties<'a> {
-    api_version: u32,
-    driver_version: u32,
-    vendor_id: u32,
-    device_id: u32,
-    device_type: DeviceType,
-    device_name: StringView<'a>,
-    pipeline_cache_uuid: [u8; VK_UUID_SIZE],
-    limits: VkPhysicalDeviceLimits,
-    sparse_properties: VkPhysicalDeviceSparseProperties,
+//read only view over the c VkPhysicalDeviceProperties, so it can be handed out by reference
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct PhysicalDeviceProperties(daxa_sys::VkPhysicalDeviceProperties);
+
+impl PhysicalDeviceProperties {
+    pub(crate) unsafe fn from_ptr<'a>(
+        ptr: *const daxa_sys::VkPhysicalDeviceProperties,
+    ) -> std::option::Option<&'a Self> {
+        ptr.cast::<Self>().as_ref()
+    }
+
+    pub(crate) fn from_c(properties: daxa_sys::VkPhysicalDeviceProperties) -> Self {
+        Self(properties)
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const daxa_sys::VkPhysicalDeviceProperties {
+        &self.0
+    }
+
+    pub fn api_version(&self) -> u32 {
+        self.0.apiVersion
+    }
+
+    pub fn driver_version(&self) -> u32 {
+        self.0.driverVersion
+    }
+
+    pub fn vendor_id(&self) -> u32 {
+        self.0.vendorID
+    }
+
+    pub fn device_id(&self) -> u32 {
+        self.0.deviceID
+    }
+
+    pub fn device_type(&self) -> DeviceType {
+        DeviceType::from_c(self.0.deviceType)
+    }
+
+    pub fn name(&self) -> &str {
+        let name = &self.0.deviceName;
+        //the driver writes a nul terminated string, but never trust it to stay in bounds
+        let bytes = unsafe { std::slice::from_raw_parts(name.as_ptr().cast::<u8>(), name.len()) };
+        ffi::CStr::from_bytes_until_nul(bytes)
+            .ok()
+            .and_then(|name| name.to_str().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn pipeline_cache_uuid(&self) -> &[u8; VK_UUID_SIZE] {
+        &self.0.pipelineCacheUUID
+    }
+
+    pub fn limits(&self) -> &VkPhysicalDeviceLimits {
+        &self.0.limits
+    }
+
+    pub fn sparse_properties(&self) -> &VkPhysicalDeviceSparseProperties {
+        &self.0.sparseProperties
+    }
 }
+
+//checks that a type handed to daxa by pointer cast or transmute matches bindgen's layout,
+//so a header change fails the build instead of corrupting memory
+macro_rules! assert_c_layout {
+    ($rust:ty, $c:ty) => {
+        const _: () = assert!(mem::size_of::<$rust>() == mem::size_of::<$c>());
+        const _: () = assert!(mem::align_of::<$rust>() == mem::align_of::<$c>());
+    };
+}
+pub(crate) use assert_c_layout;
+
+assert_c_layout!(StringView<'static>, daxa_sys::daxa_StringView);
+assert_c_layout!(ImageMipArraySlice, daxa_sys::daxa_ImageMipArraySlice);
+assert_c_layout!(ImageArraySlice, daxa_sys::daxa_ImageArraySlice);
+assert_c_layout!(ImageSlice, daxa_sys::daxa_ImageSlice);
+assert_c_layout!(Offset3D, daxa_sys::VkOffset3D);
+assert_c_layout!(Extent2D, daxa_sys::VkExtent2D);
+assert_c_layout!(Extent3D, daxa_sys::VkExtent3D);
+assert_c_layout!(Rect2D, daxa_sys::VkRect2D);
+assert_c_layout!(CClearValue, daxa_sys::daxa_ClearValue);
+assert_c_layout!(MemoryRequirements, daxa_sys::VkMemoryRequirements);
+assert_c_layout!(MemoryBlockInfo, daxa_sys::daxa_MemoryBlockInfo);
+assert_c_layout!(PhysicalDeviceProperties, daxa_sys::VkPhysicalDeviceProperties);
+assert_c_layout!(ImageLayout, daxa_sys::daxa_ImageLayout);
+assert_c_layout!(Format, daxa_sys::VkFormat);
+assert_c_layout!(CompareOp, daxa_sys::VkCompareOp);
+assert_c_layout!(SamplerAddressMode, daxa_sys::VkSamplerAddressMode);
+assert_c_layout!(ReductionMode, daxa_sys::VkSamplerReductionMode);
+assert_c_layout!(BorderColor, daxa_sys::VkBorderColor);
+assert_c_layout!(MemoryFlags, daxa_sys::daxa_MemoryFlags);
+assert_c_layout!(Access, daxa_sys::daxa_Access);
+assert_c_layout!(TimelinePair, daxa_sys::daxa_TimelinePair);
+assert_c_layout!(CCommandSubmitInfo, daxa_sys::daxa_CommandSubmitInfo);
+assert_c_layout!(CPresentInfo, daxa_sys::daxa_PresentInfo);